@@ -1,10 +1,22 @@
-use crate::commons::aoc_common::{AOCState, AocFont, BorderSize, InputLines, ScalableObject};
+use crate::commons::answers::CurrentAnswer;
+use crate::commons::aoc_common::{AocFont, InputLines, PuzzleEntity, ScalableObject};
+use crate::commons::axis::{set_axis_scale, spawn_axis, Axis, AxisOrientation};
 use crate::commons::constants::WINDOW_WIDTH;
-use bevy::prelude::*;
-use std::collections::HashSet;
+use crate::commons::event_log::EventLog;
+use crate::commons::fps::PUZZLE_DATA;
+use crate::commons::parse::LineValidator;
+use crate::commons::puzzle::{Puzzle, PuzzleInfo, PuzzleSteps, Solver};
+use crate::commons::scale::{auto_range, Scale};
+use bevy::{diagnostic::Diagnostics, prelude::*};
+use std::collections::{HashSet, VecDeque};
 
-pub fn string_to_i32(data: &str) -> i32 {
-    data.parse().unwrap()
+pub fn string_to_i32(data: &str) -> Result<i32, String> {
+    data.parse()
+        .map_err(|_| "expected a frequency change like +7 or -3".to_string())
+}
+
+fn validate_change(line: &str) -> Result<(), String> {
+    string_to_i32(line).map(|_| ())
 }
 
 fn step_calculate_part_1(answer: &mut Answer, change: i32) {
@@ -15,18 +27,16 @@ fn step_calculate_part_2(answer: &mut Answer, change: i32, vistied: &mut Visited
     answer.frequency += change;
     if vistied.vistied_frequencies.contains(&answer.frequency) {
         vistied.found = true;
-        println!("I found value {}", answer.frequency);
     } else {
         vistied.vistied_frequencies.insert(answer.frequency);
     }
 }
 
-#[derive(Default, Component )]
+#[derive(Default)]
 pub struct Answer {
     pub frequency: i32,
 }
 
-#[derive(Component)]
 pub struct VisitedNodes {
     pub vistied_frequencies: HashSet<i32>,
     pub found: bool,
@@ -44,50 +54,215 @@ impl VisitedNodes {
     }
 }
 
-pub fn setup(mut app: App) -> App {
-    app.add_system_set(
-        SystemSet::on_enter(AOCState {
-            year: 2018,
-            day: 1,
-            part: 1,
-        })
-        .with_system(app_setup),
-    );
-    app.add_system_set(
-        SystemSet::on_update(AOCState {
+/// Running frequency after each processed change, kept for the last [`CHART_POINTS`] changes.
+#[derive(Component)]
+struct FrequencyHistory {
+    values: VecDeque<i32>,
+    /// Number of values pushed so far, including the starting frequency.
+    total: usize,
+    /// Value indices at which a new pass over the input started.
+    passes: Vec<usize>,
+    /// Plot the frequencies on a symmetric log scale instead of a linear one.
+    log_scale: bool,
+}
+
+impl Default for FrequencyHistory {
+    fn default() -> Self {
+        Self {
+            values: VecDeque::from(vec![0]),
+            total: 1,
+            passes: Vec::new(),
+            log_scale: false,
+        }
+    }
+}
+
+impl FrequencyHistory {
+    fn push(&mut self, frequency: i32) {
+        self.values.push_back(frequency);
+        if self.values.len() > CHART_POINTS {
+            self.values.pop_front();
+        }
+        self.total += 1;
+    }
+
+    /// Marks the last pushed value as the end of a pass once the input wrapped around,
+    /// returning whether a pass ended.
+    fn track_pass(&mut self, input_lines: &InputLines) -> bool {
+        let ended = input_lines.wraps() > self.passes.len();
+        if ended {
+            self.passes.push(self.total - 1);
+        }
+        ended
+    }
+
+    /// Index of the oldest value still in the window.
+    fn first_index(&self) -> usize {
+        self.total - self.values.len()
+    }
+
+    /// Scales of the chart, x over the change numbers in the window and y over the
+    /// frequencies they reached.
+    fn scales(&self) -> (Scale, Scale) {
+        let first = self.first_index() as f32;
+        let x = Scale::linear(
+            (first, first + (CHART_POINTS - 1) as f32),
+            (CHART_X - CHART_WIDTH / 2.0, CHART_X + CHART_WIDTH / 2.0),
+        );
+        let domain = auto_range(self.values.iter().map(|value| *value as f32));
+        let range = (-CHART_HEIGHT / 2.0, CHART_HEIGHT / 2.0);
+        let y = if self.log_scale {
+            Scale::symlog(domain, range)
+        } else {
+            Scale::linear(domain, range).nice(6)
+        };
+        (x, y)
+    }
+}
+
+const TITLE: &str = "Chronal Calibration";
+
+pub struct Part1;
+
+impl Puzzle for Part1 {
+    fn info(&self) -> PuzzleInfo {
+        PuzzleInfo {
             year: 2018,
             day: 1,
             part: 1,
-        })
-        .with_system(step_part1_system)
-        .with_system(scale_sprite),
-    );
-    app.add_system_set(
-        SystemSet::on_enter(AOCState {
-            year: 2018,
-            day: 1,
-            part: 2,
-        })
-        .with_system(app_setup),
-    );
-    app.add_system_set(
-        SystemSet::on_update(AOCState {
+            title: TITLE,
+        }
+    }
+
+    fn setup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_setup::<Part1Solver>)
+    }
+
+    fn line_validator(&self) -> LineValidator {
+        validate_change
+    }
+
+    fn step(&self, set: SystemSet) -> SystemSet {
+        set.with_system(step_part1_system)
+            .with_system(draw_frequency_chart)
+            .with_system(toggle_log_scale)
+    }
+}
+
+pub struct Part2;
+
+impl Puzzle for Part2 {
+    fn info(&self) -> PuzzleInfo {
+        PuzzleInfo {
             year: 2018,
             day: 1,
             part: 2,
-        })
-        .with_system(step_part2_system)
-        .with_system(scale_sprite),
-    );
-    app
+            title: TITLE,
+        }
+    }
+
+    fn setup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_setup::<Part2Solver>)
+    }
+
+    fn step(&self, set: SystemSet) -> SystemSet {
+        set.with_system(step_part2_system)
+            .with_system(draw_frequency_chart)
+            .with_system(flash_repeat)
+            .with_system(toggle_log_scale)
+    }
+
+    fn measure(&self, set: SystemSet) -> SystemSet {
+        set.with_system(measure_visited_frequencies)
+    }
+
+    fn line_validator(&self) -> LineValidator {
+        validate_change
+    }
+
+    fn default_speed(&self) -> f32 {
+        // The real input only repeats after well over a hundred thousand changes
+        4096.0
+    }
 }
 
-fn app_setup(
-    mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    aoc_font: Res<AocFont>,
-) {
-    commands.insert_resource(Answer { frequency: 0 });
+#[derive(Default, Component)]
+struct Part1Solver {
+    answer: Answer,
+}
+
+impl Solver for Part1Solver {
+    fn step(&mut self, input: &mut InputLines) -> bool {
+        match input.next(false) {
+            Some(change) => {
+                // Lines are validated when the input is loaded, so none are skipped here
+                if let Ok(change) = string_to_i32(change) {
+                    step_calculate_part_1(&mut self.answer, change);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn answer(&self) -> String {
+        self.answer.frequency.to_string()
+    }
+}
+
+#[derive(Component)]
+struct Part2Solver {
+    answer: Answer,
+    visited: VisitedNodes,
+}
+
+impl Default for Part2Solver {
+    fn default() -> Self {
+        Self {
+            answer: Answer::default(),
+            visited: VisitedNodes::new(),
+        }
+    }
+}
+
+impl Solver for Part2Solver {
+    fn step(&mut self, input: &mut InputLines) -> bool {
+        if self.visited.found {
+            return false;
+        }
+        match input.next(true) {
+            Some(change) => {
+                if let Ok(change) = string_to_i32(change) {
+                    step_calculate_part_2(&mut self.answer, change, &mut self.visited);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn answer(&self) -> String {
+        self.answer.frequency.to_string()
+    }
+}
+
+const CHART_POINTS: usize = 1024;
+const CHART_WIDTH: f32 = 800.0;
+const CHART_HEIGHT: f32 = 500.0;
+/// Horizontal centre of the chart, leaving room on the left for the answer text.
+const CHART_X: f32 = 150.0;
+const MAX_PASS_MARKERS: usize = 16;
+
+#[derive(Component)]
+struct ChartSegment(usize);
+
+#[derive(Component)]
+struct PassMarker(usize);
+
+#[derive(Component)]
+struct RepeatFlash;
+
+fn app_setup<S: Solver + Component + Default>(mut commands: Commands, aoc_font: Res<AocFont>) {
     commands
         .spawn_bundle(Text2dBundle {
             text: Text::with_section(
@@ -102,64 +277,239 @@ fn app_setup(
                     horizontal: HorizontalAlign::Center,
                 },
             ),
-            transform: Transform::from_xyz(200.0 - (WINDOW_WIDTH / 2.0), 0.0, 0.0),
+            transform: Transform::from_xyz(200.0 - (WINDOW_WIDTH / 2.0), 200.0, 0.0),
             ..Default::default()
         })
-        .insert(InputLines::from_slice(include_bytes!(
-            "../../inputs/2018/day1.txt"
-        )))
-        .insert(VisitedNodes::new())
-        .insert(ScalableObject);
+        .insert(InputLines::default())
+        .insert(S::default())
+        .insert(FrequencyHistory::default())
+        .insert(ScalableObject)
+        .insert(PuzzleEntity);
     commands
         .spawn_bundle(SpriteBundle {
-            transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            transform: Transform::from_xyz(CHART_X, 0.0, 0.0),
             sprite: Sprite {
-                custom_size: Some(Vec2::new(10.0, 10.0)),
-                color: Color::rgb(1.0, 0.0, 1.0),
+                custom_size: Some(Vec2::new(CHART_WIDTH + 20.0, CHART_HEIGHT + 20.0)),
+                color: Color::rgb(0.15, 0.15, 0.15),
                 ..Default::default()
             },
             ..Default::default()
         })
-        .insert(ScalableObject);
+        .insert(ScalableObject)
+        .insert(PuzzleEntity);
+    for index in 0..CHART_POINTS - 1 {
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(1.0, 0.0, 1.0),
+                    ..Default::default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(ChartSegment(index))
+            .insert(PuzzleEntity);
+    }
+    for index in 0..MAX_PASS_MARKERS {
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(2.0, CHART_HEIGHT)),
+                    color: Color::rgba(0.5, 0.5, 0.5, 0.8),
+                    ..Default::default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(PassMarker(index))
+            .insert(PuzzleEntity);
+    }
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(CHART_WIDTH, 3.0)),
+                color: Color::GOLD,
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(CHART_X, 0.0, 2.0),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(RepeatFlash)
+        .insert(PuzzleEntity);
+    let (x, y) = FrequencyHistory::default().scales();
+    spawn_axis(
+        &mut commands,
+        Axis::horizontal(x, -CHART_HEIGHT / 2.0 - 10.0),
+    );
+    spawn_axis(
+        &mut commands,
+        Axis::vertical(y, CHART_X - CHART_WIDTH / 2.0 - 10.0),
+    );
 }
 
 fn step_part1_system(
-    mut answer: ResMut<Answer>,
-    mut query: Query<(&mut Text, &mut InputLines)>,
-    mut border_size: ResMut<BorderSize>,
+    mut steps: PuzzleSteps,
+    mut current_answer: ResMut<CurrentAnswer>,
+    mut query: Query<(
+        &mut Text,
+        &mut InputLines,
+        &mut Part1Solver,
+        &mut FrequencyHistory,
+    )>,
 ) {
-    let (mut text, mut input_line) = query.single_mut();
-    if let Some(i) = input_line.next(false) {
-        step_calculate_part_1(&mut answer, string_to_i32(i));
-        border_size.current_y = scale_log_value(answer.frequency as f32);
+    let (mut text, mut input_line, mut solver, mut history) = query.single_mut();
+    steps.run(&mut solver, &mut input_line, |solver, _, _| {
+        history.push(solver.answer.frequency);
+    });
+    current_answer.update(solver.answer.frequency);
+    text.sections[0].value = format!("answer: {}", solver.answer.frequency);
+}
+
+fn measure_visited_frequencies(mut diagnostics: ResMut<Diagnostics>, query: Query<&Part2Solver>) {
+    for solver in query.iter() {
+        let visited = solver.visited.vistied_frequencies.len();
+        diagnostics.add_measurement(PUZZLE_DATA, visited as f64);
     }
-    text.sections[0].value = format!("answer: {}", answer.frequency);
 }
 
 fn step_part2_system(
-    mut answer: ResMut<Answer>,
-    mut query: Query<(&mut Text, &mut InputLines, &mut VisitedNodes)>,
-    mut border_size: ResMut<BorderSize>,
+    mut steps: PuzzleSteps,
+    mut current_answer: ResMut<CurrentAnswer>,
+    mut event_log: ResMut<EventLog>,
+    mut query: Query<(
+        &mut Text,
+        &mut InputLines,
+        &mut Part2Solver,
+        &mut FrequencyHistory,
+    )>,
+) {
+    let (mut text, mut input_line, mut solver, mut history) = query.single_mut();
+    steps.run(&mut solver, &mut input_line, |solver, input_line, step| {
+        let pass_ended = history.track_pass(input_line);
+        history.push(solver.answer.frequency);
+        let step = Some(step);
+        if pass_ended {
+            let pass = history.passes.len();
+            event_log.push(step, format!("Pass {} over the input finished", pass));
+        }
+        if solver.visited.found {
+            let message = format!("Frequency {} reached twice", solver.answer.frequency);
+            event_log.push(step, message);
+        }
+    });
+    current_answer.update(solver.answer.frequency);
+    text.sections[0].value = format!(
+        "answer: {}\npass: {}",
+        solver.answer.frequency,
+        history.passes.len() + 1
+    );
+}
+
+type ChartSegmentQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ChartSegment,
+        &'static mut Sprite,
+        &'static mut Transform,
+        &'static mut Visibility,
+    ),
+>;
+type PassMarkerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static PassMarker,
+        &'static mut Transform,
+        &'static mut Visibility,
+    ),
+    Without<ChartSegment>,
+>;
+
+/// Redraws the chart as one rotated sprite per line segment between two values.
+fn draw_frequency_chart(
+    history_query: Query<&FrequencyHistory, Changed<FrequencyHistory>>,
+    mut segments: ChartSegmentQuery,
+    mut markers: PassMarkerQuery,
+    mut axes: Query<&mut Axis>,
 ) {
-    let (mut text, mut input_line, mut vistied_nodes) = query.single_mut();
-    for _ in 0..50 {
-        if !vistied_nodes.found {
-            if let Some(i) = input_line.next(true) {
-                step_calculate_part_2(&mut answer, string_to_i32(i), &mut vistied_nodes);
-                border_size.current_y = scale_log_value(answer.frequency as f32);
+    let history = match history_query.get_single() {
+        Ok(history) => history,
+        Err(_) => return,
+    };
+    let (x, y) = history.scales();
+    let first_index = history.first_index();
+    let point = |index: usize| {
+        Vec2::new(
+            x.map((first_index + index) as f32),
+            y.map(history.values[index] as f32),
+        )
+    };
+    for (segment, mut sprite, mut transform, mut visibility) in segments.iter_mut() {
+        let index = segment.0;
+        visibility.is_visible = index + 1 < history.values.len();
+        if !visibility.is_visible {
+            continue;
+        }
+        let start = point(index);
+        let end = point(index + 1);
+        let delta = end - start;
+        sprite.custom_size = Some(Vec2::new(delta.length(), 2.0));
+        transform.translation = ((start + end) / 2.0).extend(1.0);
+        transform.rotation = Quat::from_rotation_z(delta.y.atan2(delta.x));
+    }
+    let visible_passes = history
+        .passes
+        .iter()
+        .filter(|pass| **pass >= first_index)
+        .rev()
+        .take(MAX_PASS_MARKERS)
+        .collect::<Vec<_>>();
+    for (marker, mut transform, mut visibility) in markers.iter_mut() {
+        match visible_passes.get(marker.0) {
+            Some(pass) => {
+                transform.translation = Vec3::new(x.map(**pass as f32), 0.0, 0.5);
+                visibility.is_visible = true;
             }
+            None => visibility.is_visible = false,
         }
-        text.sections[0].value = format!("answer: {}", answer.frequency);
+    }
+    for mut axis in axes.iter_mut() {
+        let scale = match axis.orientation {
+            AxisOrientation::Horizontal => x,
+            AxisOrientation::Vertical => y,
+        };
+        set_axis_scale(&mut axis, scale);
     }
 }
 
-fn scale_sprite(answer: Res<Answer>, mut query: Query<&mut Sprite, With<ScalableObject>>) {
-    let mut sprite = query.single_mut();
-    sprite.custom_size = Some(Vec2::new(100.0, scale_log_value(answer.frequency as f32)));
+fn toggle_log_scale(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut FrequencyHistory>) {
+    if keyboard_input.just_pressed(KeyCode::L) {
+        let mut history = query.single_mut();
+        history.log_scale = !history.log_scale;
+    }
 }
 
-fn scale_log_value(data: f32) -> f32 {
-    data.log2() * 20.0
+/// Flashes a line at the first frequency reached twice once part 2 has found it.
+fn flash_repeat(
+    time: Res<Time>,
+    history_query: Query<(&Part2Solver, &FrequencyHistory)>,
+    mut flash: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<RepeatFlash>>,
+) {
+    let (solver, history) = history_query.single();
+    let (mut sprite, mut transform, mut visibility) = flash.single_mut();
+    visibility.is_visible = solver.visited.found;
+    if !solver.visited.found {
+        return;
+    }
+    let (_, y) = history.scales();
+    transform.translation.y = y.clamped().map(*history.values.back().unwrap() as f32);
+    sprite.color = if (time.seconds_since_startup() * 4.0) as u64 % 2 == 0 {
+        Color::GOLD
+    } else {
+        Color::WHITE
+    };
 }
 
 #[cfg(test)]
@@ -168,8 +518,10 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        assert_eq!(string_to_i32("+1"), 1);
-        assert_eq!(string_to_i32("-1"), -1);
+        assert_eq!(string_to_i32("+1"), Ok(1));
+        assert_eq!(string_to_i32("-1"), Ok(-1));
+        assert!(string_to_i32("1,").is_err());
+        assert!(validate_change("").is_err());
     }
 
     #[test]
@@ -185,21 +537,44 @@ mod tests {
         let mut vistied = VisitedNodes::new();
         step_calculate_part_2(&mut answer, 1, &mut vistied);
         assert_eq!(answer.frequency, 1);
-        assert_eq!(vistied.found, false);
+        assert!(!vistied.found);
         assert!(vistied.vistied_frequencies.contains(&0));
         assert!(vistied.vistied_frequencies.contains(&1));
         step_calculate_part_2(&mut answer, -1, &mut vistied);
         assert_eq!(answer.frequency, 0);
-        assert_eq!(vistied.found, true);
+        assert!(vistied.found);
+    }
+
+    #[test]
+    fn test_frequency_history() {
+        let mut inputs = InputLines::from_lines(vec!["+1".to_string(), "-2".to_string()]);
+        let mut history = FrequencyHistory::default();
+        let mut frequency = 0;
+        for _ in 0..5 {
+            frequency += string_to_i32(inputs.next(true).unwrap()).unwrap();
+            history.track_pass(&inputs);
+            history.push(frequency);
+        }
+        assert_eq!(history.values, [0, 1, -1, 0, -2, -1]);
+        assert_eq!(history.passes, [2, 4]);
+        assert_eq!(history.scales().1.domain, (-2.0, 1.0));
+
+        for _ in 0..CHART_POINTS {
+            history.push(3);
+        }
+        assert_eq!(history.values.len(), CHART_POINTS);
+        assert_eq!(history.first_index(), 6);
+        assert_eq!(history.scales().1.domain, (2.0, 4.0));
     }
 
     #[test]
     #[ignore]
     fn calculate_part1() {
-        let mut inputs = InputLines::from_slice(include_bytes!("../../inputs/2018/day1.txt"));
+        let mut inputs =
+            InputLines::from_slice(include_bytes!("../../assets/inputs/2018/day1.txt")).unwrap();
         let mut answer = Answer { frequency: 0 };
         while let Some(x) = inputs.next(false) {
-            step_calculate_part_1(&mut answer, string_to_i32(x));
+            step_calculate_part_1(&mut answer, string_to_i32(x).unwrap());
         }
         println!("{}", answer.frequency);
     }
@@ -207,12 +582,13 @@ mod tests {
     #[test]
     #[ignore]
     fn calculate_part2() {
-        let mut inputs = InputLines::from_slice(include_bytes!("../../inputs/2018/day1.txt"));
+        let mut inputs =
+            InputLines::from_slice(include_bytes!("../../assets/inputs/2018/day1.txt")).unwrap();
         let mut answer = Answer { frequency: 0 };
         let mut vistied = VisitedNodes::new();
         while !vistied.found {
             let x = inputs.next(true).unwrap();
-            step_calculate_part_2(&mut answer, string_to_i32(x), &mut vistied);
+            step_calculate_part_2(&mut answer, string_to_i32(x).unwrap(), &mut vistied);
         }
         println!("{}", answer.frequency);
         println!("{}", inputs.read_pos);
@@ -1,20 +1,30 @@
 use std::collections::HashMap;
 
-use bevy::prelude::*;
+use bevy::{diagnostic::Diagnostics, prelude::*};
 
-use crate::commons::aoc_common::{AOCName, AOCState, AocFont, InputLines};
+use crate::commons::answers::CurrentAnswer;
+use crate::commons::aoc_common::{AOCName, AocFont, InputLines, PuzzleEntity, ScalableObject};
+use crate::commons::bar_chart::{spawn_bar_chart, toggle_log_scale, Bar, BarChartSpec};
 use crate::commons::constants::WINDOW_WIDTH;
+use crate::commons::event_log::EventLog;
+use crate::commons::fps::PUZZLE_DATA;
+use crate::commons::parse::LineValidator;
+use crate::commons::puzzle::{Puzzle, PuzzleInfo, PuzzleSteps, Solver};
 
 #[derive(Default)]
 pub struct Counts {
     twos: u32,
     threes: u32,
+    /// Last ID counted, shown in the letter histogram.
+    current_id: String,
 }
 
 #[derive(Default)]
 pub struct BoxIds {
     pub ids: Vec<String>,
     pub common_id: String,
+    /// The two IDs differing by a single character, once found.
+    pub pair: Option<(String, String)>,
 }
 
 fn generate_mapping(word: &str) -> HashMap<char, u32> {
@@ -28,7 +38,16 @@ fn generate_mapping(word: &str) -> HashMap<char, u32> {
     counts
 }
 
+/// IDs are drawn one letter column per character, and the letter histogram only has a-z.
+fn validate_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || !id.chars().all(|letter| letter.is_ascii_lowercase()) {
+        return Err("expected a box ID made of the letters a-z".to_string());
+    }
+    Ok(())
+}
+
 fn step_calculate_part_1(answer: &mut Counts, id: &str) {
+    answer.current_id = id.to_string();
     let mapping = generate_mapping(id);
     if mapping.values().any(|x| *x == 3) {
         answer.threes += 1;
@@ -63,177 +82,400 @@ fn step_calculate_part_2(answer: &mut BoxIds, id: String) {
             let (is_prototype, common_str) = check_diff(box_id, &id);
             if is_prototype {
                 answer.common_id = common_str;
+                answer.pair = Some((box_id.clone(), id.clone()));
             }
         }
     }
     answer.ids.push(id);
 }
 
-pub fn setup(mut app: App) -> App {
-    app.add_system_set(
-        SystemSet::on_enter(AOCState {
-            year: 2018,
-            day: 2,
-            part: 1,
+/// Columns where two IDs have different characters.
+fn diff_columns(str1: &str, str2: &str) -> Vec<bool> {
+    str1.chars()
+        .zip(str2.chars())
+        .map(|(a, b)| a != b)
+        .collect()
+}
+
+/// One line of the diff view, an ID with the columns where it differs from the one it is
+/// compared against.
+#[derive(Debug, PartialEq)]
+struct DiffRow {
+    label: String,
+    id: String,
+    diff: Vec<bool>,
+}
+
+const DIFF_ROWS: usize = 12;
+
+/// Rows of the diff view. While searching this is the last ID read, followed by the stored
+/// IDs it was compared against that came closest, and once found the prototype pair.
+fn diff_rows(box_ids: &BoxIds) -> Vec<DiffRow> {
+    if let Some((first, second)) = &box_ids.pair {
+        return vec![
+            DiffRow {
+                label: "Pair".to_string(),
+                id: first.clone(),
+                diff: diff_columns(first, second),
+            },
+            DiffRow {
+                label: "Pair".to_string(),
+                id: second.clone(),
+                diff: diff_columns(second, first),
+            },
+        ];
+    }
+    let (current, stored) = match box_ids.ids.split_last() {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+    let mut candidates = stored
+        .iter()
+        .map(|id| {
+            let diff = diff_columns(id, current);
+            let count = diff.iter().filter(|differs| **differs).count();
+            (count, id, diff)
         })
-        .with_system(app_setup_part1),
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(count, ..)| *count);
+    let mut rows = vec![DiffRow {
+        label: "Current".to_string(),
+        id: current.clone(),
+        diff: vec![false; current.len()],
+    }];
+    rows.extend(
+        candidates
+            .into_iter()
+            .take(DIFF_ROWS)
+            .map(|(count, id, diff)| DiffRow {
+                label: format!("{} off", count),
+                id: id.clone(),
+                diff,
+            }),
     );
-    app.add_system_set(
-        SystemSet::on_update(AOCState {
+    rows
+}
+
+const TITLE: &str = "Inventory Management System";
+
+pub struct Part1;
+
+impl Puzzle for Part1 {
+    fn info(&self) -> PuzzleInfo {
+        PuzzleInfo {
             year: 2018,
             day: 2,
             part: 1,
-        })
-        .with_system(step_part1)
-        .with_system(update_sprite_part1)
-        .with_system(update_text_part1),
-    );
-    app.add_system_set(
-        SystemSet::on_enter(AOCState {
-            year: 2018,
-            day: 2,
-            part: 2,
-        })
-        .with_system(app_setup_part2),
-    );
-    app.add_system_set(
-        SystemSet::on_update(AOCState {
+            title: TITLE,
+        }
+    }
+
+    fn setup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_setup_part1)
+    }
+
+    fn step(&self, set: SystemSet) -> SystemSet {
+        set.with_system(step_part1)
+            .with_system(update_bars_part1)
+            .with_system(toggle_log_scale)
+    }
+
+    fn cleanup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_cleanup_part1)
+    }
+
+    fn line_validator(&self) -> LineValidator {
+        validate_id
+    }
+}
+
+pub struct Part2;
+
+impl Puzzle for Part2 {
+    fn info(&self) -> PuzzleInfo {
+        PuzzleInfo {
             year: 2018,
             day: 2,
             part: 2,
-        })
-        .with_system(step_part2),
-    );
-    app
+            title: TITLE,
+        }
+    }
+
+    fn setup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_setup_part2)
+    }
+
+    fn step(&self, set: SystemSet) -> SystemSet {
+        set.with_system(step_part2).with_system(update_diff_view)
+    }
+
+    fn measure(&self, set: SystemSet) -> SystemSet {
+        set.with_system(measure_box_ids)
+    }
+
+    fn cleanup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_cleanup_part2)
+    }
+
+    fn line_validator(&self) -> LineValidator {
+        validate_id
+    }
+
+    fn sample_path(&self) -> String {
+        // The puzzle description uses a different example for part 2
+        "samples/2018/day2_part2.txt".to_string()
+    }
 }
 
-fn app_setup_part1(
-    mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    aoc_font: Res<AocFont>,
-) {
-    commands
-        .spawn_bundle(SpriteBundle {
-            transform: Transform::from_xyz(-WINDOW_WIDTH / 4.0, 0.0, 0.0),
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(100.0, 0.0)),
-                color: Color::RED,
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .insert(AOCName("twos".to_owned()));
-    commands
-        .spawn_bundle(SpriteBundle {
-            transform: Transform::from_xyz(WINDOW_WIDTH / 4.0, 0.0, 0.0),
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(100.0, 0.0)),
-                color: Color::GREEN,
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .insert(AOCName("threes".to_owned()));
+impl Solver for Counts {
+    fn step(&mut self, input: &mut InputLines) -> bool {
+        match input.next(false) {
+            Some(id) => {
+                step_calculate_part_1(self, id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn answer(&self) -> String {
+        (self.twos * self.threes).to_string()
+    }
+}
+
+impl Solver for BoxIds {
+    fn step(&mut self, input: &mut InputLines) -> bool {
+        if !self.common_id.is_empty() {
+            return false;
+        }
+        match input.next(false) {
+            Some(id) => {
+                step_calculate_part_2(self, id.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn answer(&self) -> String {
+        self.common_id.clone()
+    }
+}
+
+#[derive(Component)]
+struct CountsChart;
+
+#[derive(Component)]
+struct LetterChart;
+
+fn letter_color(count: u32) -> Color {
+    match count {
+        2 => Color::RED,
+        3 => Color::GREEN,
+        _ => Color::GRAY,
+    }
+}
+
+fn app_setup_part1(mut commands: Commands, aoc_font: Res<AocFont>) {
+    let counts_chart = spawn_bar_chart(
+        &mut commands,
+        &aoc_font,
+        BarChartSpec {
+            origin: Vec2::new(-WINDOW_WIDTH / 2.0 + 80.0, -250.0),
+            height: 450.0,
+            bar_width: 100.0,
+            gap: 40.0,
+            font_size: 30.0,
+            // The checksum quickly outgrows the counts it is made of
+            log_scale: true,
+            bars: vec![
+                ("Duals", Color::RED),
+                ("Triplets", Color::GREEN),
+                ("Checksum", Color::GOLD),
+            ],
+        },
+    );
+    commands.entity(counts_chart).insert(CountsChart);
+    let letters = ('a'..='z')
+        .map(|letter| letter.to_string())
+        .collect::<Vec<_>>();
+    let letter_chart = spawn_bar_chart(
+        &mut commands,
+        &aoc_font,
+        BarChartSpec {
+            origin: Vec2::new(-20.0, -250.0),
+            height: 300.0,
+            bar_width: 18.0,
+            gap: 6.0,
+            font_size: 18.0,
+            log_scale: false,
+            bars: letters
+                .iter()
+                .map(|letter| (letter.as_str(), letter_color(0)))
+                .collect(),
+        },
+    );
+    commands.entity(letter_chart).insert(LetterChart);
     commands
         .spawn_bundle(Text2dBundle {
-            text: Text {
-                // Construct a `Vec` of `TextSection`s
-                sections: vec![
-                    TextSection {
-                        value: "Duals: ".to_string(),
-                        style: TextStyle {
-                            font: aoc_font.font_handle.clone(),
-                            font_size: 40.0,
-                            color: Color::RED,
-                        },
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: TextStyle {
-                            font: aoc_font.font_handle.clone(),
-                            font_size: 40.0,
-                            color: Color::RED,
-                        },
-                    },
-                    TextSection {
-                        value: "\nTriplets: ".to_string(),
-                        style: TextStyle {
-                            font: aoc_font.font_handle.clone(),
-                            font_size: 40.0,
-                            color: Color::GREEN,
-                        },
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: TextStyle {
-                            font: aoc_font.font_handle.clone(),
-                            font_size: 40.0,
-                            color: Color::GREEN,
-                        },
-                    },
-                    TextSection {
-                        value: "\nChecksum: ".to_string(),
-                        style: TextStyle {
-                            font: aoc_font.font_handle.clone(),
-                            font_size: 40.0,
-                            color: Color::WHITE,
-                        },
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: TextStyle {
-                            font: aoc_font.font_handle.clone(),
-                            font_size: 40.0,
-                            color: Color::WHITE,
-                        },
-                    },
-                ],
-                alignment: TextAlignment {
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: aoc_font.font_handle.clone(),
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                },
+                TextAlignment {
                     vertical: VerticalAlign::Center,
                     horizontal: HorizontalAlign::Center,
                 },
-            },
+            ),
+            transform: Transform::from_xyz(290.0, 150.0, 0.0),
             ..Default::default()
         })
-        .insert(AOCName("Output".to_string()));
+        .insert(AOCName("Output".to_string()))
+        .insert(ScalableObject)
+        .insert(PuzzleEntity);
     commands.insert_resource(Counts::default());
     commands
         .spawn()
-        .insert(InputLines::from_slice(include_bytes!(
-            "../../inputs/2018/day2.txt"
-        )));
+        .insert(InputLines::default())
+        .insert(PuzzleEntity);
 }
 
-fn step_part1(mut conuts: ResMut<Counts>, mut query: Query<&mut InputLines>) {
-    let mut line = query.single_mut();
-    if let Some(x) = line.next(false) {
-        step_calculate_part_1(&mut conuts, x)
-    }
+fn app_cleanup_part1(mut commands: Commands) {
+    commands.remove_resource::<Counts>();
 }
 
-fn update_text_part1(conuts: Res<Counts>, mut query: Query<&mut Text, With<AOCName>>) {
-    let mut text = query.single_mut();
-    text.sections[1].value = conuts.twos.to_string();
-    text.sections[3].value = conuts.threes.to_string();
-    text.sections[5].value = (conuts.twos * conuts.threes).to_string();
+fn step_part1(
+    mut steps: PuzzleSteps,
+    mut conuts: ResMut<Counts>,
+    mut current_answer: ResMut<CurrentAnswer>,
+    mut query: Query<&mut InputLines>,
+) {
+    let mut line = query.single_mut();
+    steps.run(&mut conuts, &mut line, |_, _, _| {});
+    current_answer.update(conuts.answer());
 }
 
-fn update_sprite_part1(conuts: Res<Counts>, mut query: Query<(&mut Sprite, &AOCName)>) {
-    for (mut sprite, name) in query.iter_mut() {
-        if name.0 == "twos" {
-            if let Some(mut size) = sprite.custom_size {
-                size.y = conuts.twos as f32;
-            }
+fn update_bars_part1(
+    conuts: Res<Counts>,
+    counts_chart: Query<&Children, With<CountsChart>>,
+    letter_chart: Query<&Children, With<LetterChart>>,
+    mut bars: Query<&mut Bar>,
+    mut query: Query<&mut Text, With<AOCName>>,
+) {
+    if !conuts.is_changed() {
+        return;
+    }
+    for child in counts_chart.single().iter() {
+        if let Ok(mut bar) = bars.get_mut(*child) {
+            bar.value = match bar.index {
+                0 => conuts.twos,
+                1 => conuts.threes,
+                _ => conuts.twos * conuts.threes,
+            } as f32;
         }
-        if name.0 == "threes" {
-            if let Some(mut size) = sprite.custom_size {
-                size.y = conuts.threes as f32;
-            }
+    }
+    let mapping = generate_mapping(&conuts.current_id);
+    for child in letter_chart.single().iter() {
+        if let Ok(mut bar) = bars.get_mut(*child) {
+            let letter = (b'a' + bar.index as u8) as char;
+            let count = mapping.get(&letter).copied().unwrap_or(0);
+            bar.value = count as f32;
+            bar.color = letter_color(count);
         }
     }
+    query.single_mut().sections[0].value = format!("Letters of {}", conuts.current_id);
+}
+
+/// Character at `column` of the diff view line `row`, row 0 being the top line.
+#[derive(Component)]
+struct DiffCell {
+    row: usize,
+    column: usize,
+}
+
+#[derive(Component)]
+struct DiffRowLabel(usize);
+
+/// Highlights the differing column of the prototype pair.
+#[derive(Component)]
+struct ColumnMarker;
+
+/// The font is not monospaced, so every character gets its own text to keep columns aligned.
+const CELL_WIDTH: f32 = 28.0;
+const ROW_HEIGHT: f32 = 36.0;
+const MAX_COLUMNS: usize = 26;
+const DIFF_LEFT: f32 = -(MAX_COLUMNS as f32) * CELL_WIDTH / 2.0 + 100.0;
+const DIFF_TOP: f32 = 280.0;
+const DIM_COLOR: Color = Color::rgb(0.35, 0.35, 0.35);
+
+fn diff_cell_position(row: usize, column: usize) -> Vec3 {
+    // Leave a gap between the current ID and the ones it is compared against
+    let gap = if row > 0 { ROW_HEIGHT / 2.0 } else { 0.0 };
+    Vec3::new(
+        DIFF_LEFT + column as f32 * CELL_WIDTH,
+        DIFF_TOP - row as f32 * ROW_HEIGHT - gap,
+        1.0,
+    )
 }
 
 fn app_setup_part2(mut commands: Commands, aoc_font: Res<AocFont>) {
+    let text_style = TextStyle {
+        font: aoc_font.font_handle.clone(),
+        font_size: 30.0,
+        color: Color::WHITE,
+    };
+    let alignment = TextAlignment {
+        vertical: VerticalAlign::Center,
+        horizontal: HorizontalAlign::Center,
+    };
+    for row in 0..=DIFF_ROWS {
+        for column in 0..MAX_COLUMNS {
+            commands
+                .spawn_bundle(Text2dBundle {
+                    text: Text::with_section("", text_style.clone(), alignment),
+                    transform: Transform::from_translation(diff_cell_position(row, column)),
+                    ..Default::default()
+                })
+                .insert(DiffCell { row, column })
+                .insert(ScalableObject)
+                .insert(PuzzleEntity);
+        }
+        let label_position = diff_cell_position(row, 0) - Vec3::new(CELL_WIDTH, 0.0, 0.0);
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::with_section(
+                    "",
+                    TextStyle {
+                        color: DIM_COLOR,
+                        ..text_style.clone()
+                    },
+                    TextAlignment {
+                        vertical: VerticalAlign::Center,
+                        horizontal: HorizontalAlign::Right,
+                    },
+                ),
+                transform: Transform::from_translation(label_position),
+                ..Default::default()
+            })
+            .insert(DiffRowLabel(row))
+            .insert(PuzzleEntity);
+    }
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(1.0, 0.84, 0.0, 0.3),
+                // Covers both rows of the pair and the gap between them
+                custom_size: Some(Vec2::new(CELL_WIDTH, ROW_HEIGHT * 2.5)),
+                ..Default::default()
+            },
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(ColumnMarker)
+        .insert(PuzzleEntity);
     commands
         .spawn_bundle(Text2dBundle {
             text: Text {
@@ -261,24 +503,98 @@ fn app_setup_part2(mut commands: Commands, aoc_font: Res<AocFont>) {
                     horizontal: HorizontalAlign::Center,
                 },
             },
+            transform: Transform::from_xyz(0.0, -300.0, 0.0),
             ..Default::default()
         })
         .insert(AOCName("Output".to_string()))
-        .insert(InputLines::from_slice(include_bytes!(
-            "../../inputs/2018/day2.txt"
-        )));
+        .insert(ScalableObject)
+        .insert(InputLines::default())
+        .insert(PuzzleEntity);
     commands.insert_resource(BoxIds::default());
 }
 
-fn step_part2(mut counts: ResMut<BoxIds>, mut query: Query<(&mut InputLines, &mut Text)>) {
+fn app_cleanup_part2(mut commands: Commands) {
+    commands.remove_resource::<BoxIds>();
+}
+
+fn measure_box_ids(mut diagnostics: ResMut<Diagnostics>, box_ids: Res<BoxIds>) {
+    diagnostics.add_measurement(PUZZLE_DATA, box_ids.ids.len() as f64);
+}
+
+fn step_part2(
+    mut steps: PuzzleSteps,
+    mut counts: ResMut<BoxIds>,
+    mut event_log: ResMut<EventLog>,
+    mut query: Query<(&mut InputLines, &mut Text)>,
+) {
     let (mut line, mut text) = query.single_mut();
-    if let Some(x) = line.next(false) {
-        step_calculate_part_2(&mut counts, x.to_string());
-        if counts.common_id.is_empty() {
-            text.sections[1].value = x.to_string();
-        } else {
-            text.sections[1].value = counts.common_id.to_string();
+    steps.run(&mut counts, &mut line, |counts, _, step| {
+        if let Some((first, second)) = &counts.pair {
+            let message = format!("Prototype boxes {} and {} matched", first, second);
+            event_log.push(Some(step), message);
         }
+    });
+    if !counts.common_id.is_empty() {
+        text.sections[1].value = counts.common_id.to_string();
+    }
+}
+
+type DiffLabelQuery<'w, 's> =
+    Query<'w, 's, (&'static DiffRowLabel, &'static mut Text), Without<DiffCell>>;
+
+/// Shows the current ID against the closest stored IDs, dimming the matching characters.
+/// Once the prototype pair is found only the pair is shown, with its differing column marked.
+fn update_diff_view(
+    box_ids: Res<BoxIds>,
+    mut cells: Query<(&DiffCell, &mut Text)>,
+    mut labels: DiffLabelQuery,
+    mut marker: Query<(&mut Transform, &mut Visibility), With<ColumnMarker>>,
+) {
+    if !box_ids.is_changed() {
+        return;
+    }
+    let found = box_ids.pair.is_some();
+    let rows = diff_rows(&box_ids);
+    for (cell, mut text) in cells.iter_mut() {
+        let (value, color) = match rows.get(cell.row) {
+            Some(row) => {
+                let value = row
+                    .id
+                    .chars()
+                    .nth(cell.column)
+                    .map(|letter| letter.to_string())
+                    .unwrap_or_default();
+                let differs = row.diff.get(cell.column).copied().unwrap_or(false);
+                let color = match (found, differs) {
+                    (true, true) => Color::GOLD,
+                    (true, false) => Color::WHITE,
+                    (false, true) => Color::RED,
+                    (false, false) if cell.row == 0 => Color::WHITE,
+                    (false, false) => DIM_COLOR,
+                };
+                (value, color)
+            }
+            None => (String::new(), Color::WHITE),
+        };
+        text.sections[0].value = value;
+        text.sections[0].style.color = color;
+    }
+    for (label, mut text) in labels.iter_mut() {
+        text.sections[0].value = rows
+            .get(label.0)
+            .map(|row| row.label.clone())
+            .unwrap_or_default();
+    }
+    let (mut transform, mut visibility) = marker.single_mut();
+    let column = rows
+        .first()
+        .filter(|_| found)
+        .and_then(|row| row.diff.iter().position(|differs| *differs));
+    visibility.is_visible = column.is_some();
+    if let Some(column) = column {
+        let top = diff_cell_position(0, column);
+        let bottom = diff_cell_position(1, column);
+        transform.translation = Vec3::new(top.x, (top.y + bottom.y) / 2.0, 0.0);
     }
 }
 
@@ -321,10 +637,43 @@ mod test {
         assert!(!check_diff("klmno", "axcye").0);
     }
 
+    #[test]
+    fn test_validate_id() {
+        assert!(validate_id("abcdef").is_ok());
+        assert!(validate_id("").is_err());
+        assert!(validate_id("abc def").is_err());
+        assert!(validate_id("abcDef").is_err());
+    }
+
+    #[test]
+    fn test_diff_rows() {
+        let mut box_ids = BoxIds::default();
+        assert!(diff_rows(&box_ids).is_empty());
+        for id in ["abcde", "fghij", "klmno", "fguij"] {
+            step_calculate_part_2(&mut box_ids, id.to_string());
+            if box_ids.pair.is_none() {
+                let rows = diff_rows(&box_ids);
+                assert_eq!(rows[0].id, id);
+                assert_eq!(rows.len(), box_ids.ids.len());
+            }
+        }
+        let rows = diff_rows(&box_ids);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].id, "fghij");
+        assert_eq!(rows[1].id, "fguij");
+        assert_eq!(rows[0].diff, [false, false, true, false, false]);
+
+        box_ids.pair = None;
+        let rows = diff_rows(&box_ids);
+        assert_eq!(rows[1].label, "1 off");
+        assert_eq!(rows[1].id, "fghij");
+    }
+
     #[test]
     #[ignore]
     fn calculate_part1() {
-        let mut inputs = InputLines::from_slice(include_bytes!("../../inputs/2018/day2.txt"));
+        let mut inputs =
+            InputLines::from_slice(include_bytes!("../../assets/inputs/2018/day2.txt")).unwrap();
         let mut count = Counts::default();
         while let Some(x) = inputs.next(false) {
             step_calculate_part_1(&mut count, x);
@@ -335,7 +684,8 @@ mod test {
     #[test]
     #[ignore]
     fn calculate_part2() {
-        let mut inputs = InputLines::from_slice(include_bytes!("../../inputs/2018/day2.txt"));
+        let mut inputs =
+            InputLines::from_slice(include_bytes!("../../assets/inputs/2018/day2.txt")).unwrap();
         let mut box_ids = BoxIds::default();
         println!("{:?}", box_ids.ids);
         println!("{}", box_ids.common_id);
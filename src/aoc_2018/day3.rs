@@ -1,10 +1,22 @@
-use bevy::prelude::*;
+use bevy::{
+    diagnostic::Diagnostics,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
 
-use crate::commons::aoc_common::{AOCState, AocFont, InputLines};
+use crate::commons::answers::CurrentAnswer;
+use crate::commons::aoc_common::{AocFont, InputLines, PuzzleEntity, ScalableObject};
+use crate::commons::constants::WINDOW_WIDTH;
+use crate::commons::event_log::EventLog;
+use crate::commons::fps::PUZZLE_DATA;
+use crate::commons::parse::LineValidator;
+use crate::commons::puzzle::{Puzzle, PuzzleInfo, PuzzleSteps, Solver};
 
-#[derive(Debug, Component)]
+#[derive(Debug, Default)]
 struct Fabric {
     pub grid: Vec<Vec<u16>>,
+    /// Square inches covered by two or more claims.
+    pub overlaps: u64,
 }
 
 impl Fabric {
@@ -14,22 +26,34 @@ impl Fabric {
             grid.push((0..size).map(|_| 0).collect::<Vec<u16>>());
         }
 
-        Self { grid }
+        Self { grid, overlaps: 0 }
     }
 
-    fn create_string(&self) -> String {
-        let mut string = String::new();
-        for line in &self.grid {
-            for number in line {
-                if *number > 0 {
-                    string.push_str("  .  ");
-                } else {
-                    string.push_str("     ");
-                }
-            }
-            string.push_str("\n");
+    /// Smallest square fabric holding every claim of the input, so the example is not lost
+    /// in a corner of a full size fabric.
+    fn for_input(lines: &[String]) -> Self {
+        let size = lines
+            .iter()
+            .filter_map(|line| Claim::parse_command(line).ok())
+            .flat_map(|claim| {
+                (0..2).map(move |axis| claim.pos[axis] as u32 + claim.size[axis] as u32)
+            })
+            .max()
+            .unwrap_or(0);
+        Self::new(size.max(1))
+    }
+
+    /// Sizes the fabric from the input once it has loaded, returning whether it did.
+    fn fit(&mut self, input: &InputLines) -> bool {
+        if !self.grid.is_empty() || !input.loaded {
+            return false;
         }
-        string
+        *self = Self::for_input(&input.input_lines);
+        true
+    }
+
+    fn size(&self) -> u32 {
+        self.grid.len() as u32
     }
 }
 
@@ -41,47 +65,69 @@ struct Claim {
 }
 
 impl Claim {
-    pub fn parse_command(claim_str: &str) -> Claim {
-        let mut claim_str_iter = claim_str.split(" ");
-        let id_str = claim_str_iter.next().unwrap();
-        claim_str_iter.next();
-        let pos_str = claim_str_iter.next().unwrap();
-        let size_str = claim_str_iter.next().unwrap();
-
-        let id = id_str[1..].parse::<u16>().unwrap();
-        let pos = pos_str[..pos_str.len() - 1]
-            .split(',')
-            .map(|x| x.parse::<u16>().unwrap())
-            .collect::<Vec<u16>>();
-        let size = size_str
-            .split('x')
-            .map(|x| x.parse::<u16>().unwrap())
-            .collect::<Vec<u16>>();
-
-        Claim {
-            id,
-            pos: pos.try_into().unwrap(),
-            size: size.try_into().unwrap(),
-        }
+    pub fn parse_command(claim_str: &str) -> Result<Claim, String> {
+        let invalid = || "expected a claim like #1 @ 1,3: 4x4".to_string();
+        let mut claim_str_iter = claim_str.split(' ');
+        let (id_str, pos_str, size_str) = match (
+            claim_str_iter.next(),
+            claim_str_iter.next(),
+            claim_str_iter.next(),
+            claim_str_iter.next(),
+            claim_str_iter.next(),
+        ) {
+            (Some(id_str), Some("@"), Some(pos_str), Some(size_str), None) => {
+                (id_str, pos_str, size_str)
+            }
+            _ => return Err(invalid()),
+        };
+
+        let id = id_str
+            .strip_prefix('#')
+            .and_then(|id| id.parse::<u16>().ok())
+            .ok_or_else(invalid)?;
+        let pos = pos_str
+            .strip_suffix(':')
+            .and_then(|pos| parse_pair(pos, ','))
+            .ok_or_else(invalid)?;
+        let size = parse_pair(size_str, 'x').ok_or_else(invalid)?;
+
+        Ok(Claim { id, pos, size })
     }
 }
 
-fn update_fabric_wth_claim(fabric: &mut Fabric, claim: Claim) {
+fn parse_pair(pair_str: &str, separator: char) -> Option<[u16; 2]> {
+    let (first, second) = pair_str.split_once(separator)?;
+    Some([first.parse().ok()?, second.parse().ok()?])
+}
+
+/// Side of the largest fabric claims may extend to, the one of the puzzle.
+const MAX_FABRIC_SIZE: u32 = 1000;
+
+fn validate_claim(claim_str: &str) -> Result<(), String> {
+    let claim = Claim::parse_command(claim_str)?;
+    let fits =
+        (0..2).all(|axis| claim.pos[axis] as u32 + claim.size[axis] as u32 <= MAX_FABRIC_SIZE);
+    if !fits {
+        return Err(format!(
+            "claim does not fit on the {0}x{0} fabric",
+            MAX_FABRIC_SIZE
+        ));
+    }
+    Ok(())
+}
+
+fn update_fabric_wth_claim(fabric: &mut Fabric, claim: &Claim) {
     for x in 0..claim.size[0] as usize {
         for y in 0..claim.size[1] as usize {
-            fabric.grid[claim.pos[1] as usize + y][claim.pos[0] as usize + x] += 1;
+            let count = &mut fabric.grid[claim.pos[1] as usize + y][claim.pos[0] as usize + x];
+            *count += 1;
+            if *count == 2 {
+                fabric.overlaps += 1;
+            }
         }
     }
 }
 
-fn get_overlaps(fabric: &Fabric) -> u64 {
-    fabric
-        .grid
-        .iter()
-        .map(|x| x.iter().map(|y| (*y >= 2) as u64).sum::<u64>())
-        .sum::<u64>()
-}
-
 fn check_non_overlapping_claim(fabric: &Fabric, claim: &Claim) -> bool {
     for x in 0..claim.size[0] as usize {
         for y in 0..claim.size[1] as usize {
@@ -93,76 +139,438 @@ fn check_non_overlapping_claim(fabric: &Fabric, claim: &Claim) -> bool {
     true
 }
 
-pub fn setup(mut app: App) -> App {
-    app.add_system_set(
-        SystemSet::on_enter(AOCState {
-            year: 2018,
-            day: 3,
-            part: 1,
+/// Side of the drawn fabric whatever its size in inches, centred to the right of the status
+/// text.
+const FABRIC_PIXELS: f32 = 650.0;
+const FABRIC_OFFSET_X: f32 = 150.0;
+
+/// Heatmap of the fabric, one pixel per square inch coloured by how many claims cover it.
+#[derive(Component)]
+struct FabricImage(Handle<Image>);
+
+fn overlap_color(count: u16) -> [u8; 4] {
+    match count {
+        0 => [38, 38, 38, 255],
+        1 => [40, 90, 160, 255],
+        2 => [230, 160, 40, 255],
+        _ => [220, 40, 40, 255],
+    }
+}
+
+fn new_fabric_image(size: u32) -> Image {
+    Image::new_fill(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &overlap_color(0),
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// The image is a single inch until the input has loaded and the fabric is sized.
+fn spawn_fabric_image(commands: &mut Commands, images: &mut Assets<Image>) {
+    let handle = images.add(new_fabric_image(1));
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(FABRIC_PIXELS, FABRIC_PIXELS)),
+                ..Default::default()
+            },
+            texture: handle.clone(),
+            transform: Transform::from_xyz(FABRIC_OFFSET_X, 0.0, 0.0),
+            ..Default::default()
         })
-        .with_system(app_setup_part1),
-    );
-    app.add_system_set(
-        SystemSet::on_update(AOCState {
+        .insert(FabricImage(handle))
+        .insert(ScalableObject)
+        .insert(PuzzleEntity);
+}
+
+/// Repaints the pixels under the claim, the rest of the image is left untouched.
+fn paint_claim(image: &mut Image, fabric: &Fabric, claim: &Claim) {
+    let width = fabric.grid.len();
+    for y in claim.pos[1] as usize..(claim.pos[1] + claim.size[1]) as usize {
+        for x in claim.pos[0] as usize..(claim.pos[0] + claim.size[0]) as usize {
+            let pixel = (y * width + x) * 4;
+            image.data[pixel..pixel + 4].copy_from_slice(&overlap_color(fabric.grid[y][x]));
+        }
+    }
+}
+
+fn paint_fabric(image: &mut Image, fabric: &Fabric) {
+    for (pixel, count) in image
+        .data
+        .chunks_exact_mut(4)
+        .zip(fabric.grid.iter().flatten())
+    {
+        pixel.copy_from_slice(&overlap_color(*count));
+    }
+}
+
+pub struct Part1;
+
+impl Puzzle for Part1 {
+    fn info(&self) -> PuzzleInfo {
+        PuzzleInfo {
             year: 2018,
             day: 3,
             part: 1,
-        })
-        .with_system(step_part1),
-    );
-    app
+            title: "No Matter How You Slice It",
+        }
+    }
+
+    fn setup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_setup_part1)
+    }
+
+    fn step(&self, set: SystemSet) -> SystemSet {
+        set.with_system(step_part1)
+    }
+
+    fn measure(&self, set: SystemSet) -> SystemSet {
+        set.with_system(measure_fabric_part1)
+    }
+
+    fn line_validator(&self) -> LineValidator {
+        validate_claim
+    }
+}
+
+#[derive(Component)]
+struct Part1Solver {
+    fabric: Fabric,
+    /// Claim applied by the last step, painted onto the heatmap.
+    claim: Option<Claim>,
+}
+
+impl Part1Solver {
+    fn new() -> Self {
+        Self {
+            fabric: Fabric::default(),
+            claim: None,
+        }
+    }
+}
+
+impl Solver for Part1Solver {
+    fn step(&mut self, input: &mut InputLines) -> bool {
+        self.fabric.fit(input);
+        match input.next(false) {
+            Some(claim_str) => {
+                // Lines are validated when the input is loaded, so none are skipped here
+                self.claim = Claim::parse_command(claim_str).ok();
+                if let Some(claim) = &self.claim {
+                    update_fabric_wth_claim(&mut self.fabric, claim);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn answer(&self) -> String {
+        self.fabric.overlaps.to_string()
+    }
 }
 
-fn app_setup_part1(mut commands: Commands, aoc_font: Res<AocFont>) {
+fn app_setup_part1(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    aoc_font: Res<AocFont>,
+) {
+    spawn_fabric_image(&mut commands, &mut images);
     commands
         .spawn_bundle(Text2dBundle {
             text: Text {
-                sections: vec![create_text_section(100, &aoc_font)],
+                sections: vec![
+                    TextSection {
+                        value: "Overlaps: ".to_string(),
+                        style: TextStyle {
+                            font: aoc_font.font_handle.clone(),
+                            font_size: 40.0,
+                            color: Color::WHITE,
+                        },
+                    },
+                    TextSection {
+                        value: "0".to_string(),
+                        style: TextStyle {
+                            font: aoc_font.font_handle.clone(),
+                            font_size: 40.0,
+                            color: Color::GOLD,
+                        },
+                    },
+                ],
                 alignment: TextAlignment {
                     vertical: VerticalAlign::Center,
                     horizontal: HorizontalAlign::Center,
                 },
             },
-            transform: Transform::from_xyz(-150.0, 0.0, 0.0),
+            transform: Transform::from_xyz(-WINDOW_WIDTH / 2.0 + 200.0, 0.0, 0.0),
             ..Default::default()
         })
-        .insert(Fabric::new(1000))
-        .insert(InputLines::from_slice(include_bytes!(
-            "../../inputs/2018/day3.txt"
-        )));
+        .insert(Part1Solver::new())
+        .insert(InputLines::default())
+        .insert(ScalableObject)
+        .insert(PuzzleEntity);
+}
+
+fn step_part1(
+    mut steps: PuzzleSteps,
+    mut current_answer: ResMut<CurrentAnswer>,
+    mut images: ResMut<Assets<Image>>,
+    mut query: Query<(&mut InputLines, &mut Part1Solver, &mut Text)>,
+    fabric_image: Query<&FabricImage>,
+) {
+    let (mut line, mut solver, mut text) = query.single_mut();
+    let mut image = images.get_mut(&fabric_image.single().0);
+    if solver.fabric.fit(&line) {
+        if let Some(image) = image.as_mut() {
+            **image = new_fabric_image(solver.fabric.size());
+        }
+    }
+    let updated = steps.run(&mut solver, &mut line, |solver, _, _| {
+        if let (Some(image), Some(claim)) = (image.as_mut(), &solver.claim) {
+            paint_claim(image, &solver.fabric, claim);
+        }
+    });
+    if updated {
+        let overlaps = solver.answer();
+        text.sections[1].value = overlaps.clone();
+        current_answer.update(overlaps);
+    }
+}
+
+/// Reports the square inches of fabric tracked, which grows with the claims of the input.
+fn measure_fabric(diagnostics: &mut Diagnostics, fabric: &Fabric) {
+    let size = fabric.size() as f64;
+    diagnostics.add_measurement(PUZZLE_DATA, size * size);
+}
+
+fn measure_fabric_part1(mut diagnostics: ResMut<Diagnostics>, query: Query<&Part1Solver>) {
+    for solver in query.iter() {
+        measure_fabric(&mut diagnostics, &solver.fabric);
+    }
 }
 
-fn step_part1(mut query: Query<(&mut InputLines, &mut Fabric, &mut Text)>) {
-    let (mut line, mut fabric, mut text) = query.single_mut();
-    if let Some(claim_str) = line.next(false) {
-        update_fabric_wth_claim(&mut fabric, Claim::parse_command(claim_str));
-        //text.sections[0].value = fabric.create_string();
-        println!("{}", get_overlaps(&fabric));
+pub struct Part2;
+
+impl Puzzle for Part2 {
+    fn info(&self) -> PuzzleInfo {
+        PuzzleInfo {
+            year: 2018,
+            day: 3,
+            part: 2,
+            title: "No Matter How You Slice It",
+        }
+    }
+
+    fn setup(&self, set: SystemSet) -> SystemSet {
+        set.with_system(app_setup_part2)
+    }
+
+    fn step(&self, set: SystemSet) -> SystemSet {
+        set.with_system(step_part2)
+    }
+
+    fn measure(&self, set: SystemSet) -> SystemSet {
+        set.with_system(measure_fabric_part2)
+    }
+
+    fn line_validator(&self) -> LineValidator {
+        validate_claim
     }
 }
 
-fn format_text(number: u16) -> String {
-    format!("{:0>4} ", number)
+/// Part 2 first fills the fabric with every claim, then reads the claims again until one
+/// of them does not overlap any other.
+#[derive(Component)]
+struct ClaimScan {
+    fabric: Fabric,
+    scanning: bool,
+    intact: Option<u16>,
+    /// Claim checked by the last step while scanning, and whether it was intact.
+    checked: Option<(Claim, bool)>,
+    /// Every claim was checked without finding an intact one.
+    exhausted: bool,
+}
+
+impl ClaimScan {
+    fn new() -> Self {
+        Self {
+            fabric: Fabric::default(),
+            scanning: false,
+            intact: None,
+            checked: None,
+            exhausted: false,
+        }
+    }
 }
 
-fn create_text_section(size: u32, aoc_font: &Res<AocFont>) -> TextSection {
-    let mut final_text = String::new();
-    for j in 0..size {
-        for i in 0..size {
-            let mut text = "  .  ".to_string();
-            if i == (size - 1) {
-                text.push('\n');
+impl Solver for ClaimScan {
+    fn step(&mut self, input: &mut InputLines) -> bool {
+        if self.intact.is_some() || self.exhausted {
+            return false;
+        }
+        self.fabric.fit(input);
+        let claim = input
+            .next(false)
+            .map(|claim_str| Claim::parse_command(claim_str));
+        if !self.scanning {
+            match claim {
+                Some(Ok(claim)) => update_fabric_wth_claim(&mut self.fabric, &claim),
+                Some(Err(_)) => {}
+                None => {
+                    input.refresh();
+                    self.scanning = true;
+                }
+            }
+            return true;
+        }
+        match claim {
+            Some(Err(_)) => self.checked = None,
+            Some(Ok(claim)) => {
+                let intact = check_non_overlapping_claim(&self.fabric, &claim);
+                if intact {
+                    self.intact = Some(claim.id);
+                }
+                self.checked = Some((claim, intact));
+            }
+            None => {
+                self.exhausted = true;
+                return false;
             }
-            final_text.push_str(&text)
         }
+        true
     }
-    TextSection {
-        value: final_text,
-        style: TextStyle {
-            font: aoc_font.font_handle.clone(),
-            font_size: 7.0,
-            color: Color::WHITE,
-        },
+
+    fn answer(&self) -> String {
+        self.intact
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    }
+}
+
+#[derive(Component)]
+struct ClaimHighlight;
+
+fn app_setup_part2(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    aoc_font: Res<AocFont>,
+) {
+    spawn_fabric_image(&mut commands, &mut images);
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::ZERO),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(FABRIC_OFFSET_X, 0.0, 1.0),
+            ..Default::default()
+        })
+        .insert(ClaimHighlight)
+        .insert(PuzzleEntity);
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![
+                    TextSection {
+                        value: "Filling fabric".to_string(),
+                        style: TextStyle {
+                            font: aoc_font.font_handle.clone(),
+                            font_size: 40.0,
+                            color: Color::WHITE,
+                        },
+                    },
+                    TextSection {
+                        value: "".to_string(),
+                        style: TextStyle {
+                            font: aoc_font.font_handle.clone(),
+                            font_size: 40.0,
+                            color: Color::GOLD,
+                        },
+                    },
+                ],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center,
+                },
+            },
+            transform: Transform::from_xyz(-WINDOW_WIDTH / 2.0 + 200.0, 0.0, 0.0),
+            ..Default::default()
+        })
+        .insert(ClaimScan::new())
+        .insert(InputLines::default())
+        .insert(ScalableObject)
+        .insert(PuzzleEntity);
+}
+
+fn step_part2(
+    mut steps: PuzzleSteps,
+    mut event_log: ResMut<EventLog>,
+    mut images: ResMut<Assets<Image>>,
+    mut query: Query<(&mut InputLines, &mut ClaimScan, &mut Text)>,
+    mut highlight: Query<(&mut Sprite, &mut Transform), With<ClaimHighlight>>,
+    fabric_image: Query<&FabricImage>,
+) {
+    let (mut line, mut scan, mut text) = query.single_mut();
+    if !line.loaded {
+        return;
+    }
+    // Filling the fabric is not shown, only the scan over the filled fabric is played back
+    if !scan.scanning {
+        while !scan.scanning {
+            steps.step(&mut scan, &mut line);
+        }
+        if let Some(image) = images.get_mut(&fabric_image.single().0) {
+            *image = new_fabric_image(scan.fabric.size());
+            paint_fabric(image, &scan.fabric);
+        }
+    }
+    let exhausted = scan.exhausted;
+    let checked = steps.run(&mut scan, &mut line, |scan, _, step| {
+        if let Some((claim, true)) = &scan.checked {
+            let message = format!("Claim #{} overlaps no other claim", claim.id);
+            event_log.push(Some(step), message);
+        }
+    });
+    if scan.exhausted && !exhausted {
+        event_log.push(Some(steps.elapsed()), "Every claim overlaps another one");
+        text.sections[0].value = "Intact claim: ".to_string();
+        text.sections[1].value = "none".to_string();
+    }
+    if let (true, Some((claim, intact))) = (checked, &scan.checked) {
+        let (mut sprite, mut transform) = highlight.single_mut();
+        // Translucent so the heatmap under the claim stays visible
+        sprite.color = if *intact {
+            Color::rgba(0.0, 1.0, 0.0, 0.6)
+        } else {
+            Color::rgba(1.0, 0.0, 0.0, 0.6)
+        };
+        let scale = FABRIC_PIXELS / scan.fabric.size() as f32;
+        sprite.custom_size = Some(Vec2::new(
+            claim.size[0] as f32 * scale,
+            claim.size[1] as f32 * scale,
+        ));
+        let half = scan.fabric.size() as f32 / 2.0;
+        let center_x = claim.pos[0] as f32 + claim.size[0] as f32 / 2.0 - half;
+        let center_y = claim.pos[1] as f32 + claim.size[1] as f32 / 2.0 - half;
+        transform.translation.x = FABRIC_OFFSET_X + center_x * scale;
+        transform.translation.y = -center_y * scale;
+        text.sections[0].value = if *intact {
+            "Intact claim: ".to_string()
+        } else {
+            "Checking claim: ".to_string()
+        };
+        text.sections[1].value = format!("#{}", claim.id);
+    }
+}
+
+fn measure_fabric_part2(mut diagnostics: ResMut<Diagnostics>, query: Query<&ClaimScan>) {
+    for scan in query.iter() {
+        measure_fabric(&mut diagnostics, &scan.fabric);
     }
 }
 
@@ -176,29 +584,35 @@ mod test {
     fn test_parse_command() {
         assert_eq!(
             Claim::parse_command("#1 @ 1,3: 4x4"),
-            Claim {
+            Ok(Claim {
                 id: 1,
                 pos: [1, 3],
                 size: [4, 4]
-            }
+            })
         );
         assert_eq!(
             Claim::parse_command("#100 @ 104,310: 40x400"),
-            Claim {
+            Ok(Claim {
                 id: 100,
                 pos: [104, 310],
                 size: [40, 400]
-            }
+            })
         );
+        assert!(Claim::parse_command("#1 @ 1,3 4x4").is_err());
+        assert!(Claim::parse_command("#1 @ 1,3: 4x").is_err());
+        assert!(Claim::parse_command("1 @ 1,3: 4x4").is_err());
+        assert!(Claim::parse_command("#1 @ 1,3: 4x4 extra").is_err());
+        assert!(validate_claim("#1 @ 1,3: 4x4").is_ok());
+        assert!(validate_claim("#1 @ 998,3: 4x4").is_err());
     }
 
     #[test]
     fn test_update_fabric() {
         let mut fabric = Fabric::new(8);
         println!("{:?}", fabric);
-        update_fabric_wth_claim(&mut fabric, Claim::parse_command("#1 @ 1,3: 4x4"));
-        update_fabric_wth_claim(&mut fabric, Claim::parse_command("#2 @ 3,1: 4x4"));
-        update_fabric_wth_claim(&mut fabric, Claim::parse_command("#3 @ 5,5: 2x2"));
+        update_fabric_wth_claim(&mut fabric, &Claim::parse_command("#1 @ 1,3: 4x4").unwrap());
+        update_fabric_wth_claim(&mut fabric, &Claim::parse_command("#2 @ 3,1: 4x4").unwrap());
+        update_fabric_wth_claim(&mut fabric, &Claim::parse_command("#3 @ 5,5: 2x2").unwrap());
 
         assert_eq!(fabric.grid[0], [0, 0, 0, 0, 0, 0, 0, 0]);
         assert_eq!(fabric.grid[1], [0, 0, 0, 1, 1, 1, 1, 0]);
@@ -209,59 +623,90 @@ mod test {
         assert_eq!(fabric.grid[6], [0, 1, 1, 1, 1, 1, 1, 0]);
         assert_eq!(fabric.grid[7], [0, 0, 0, 0, 0, 0, 0, 0]);
 
-        assert_eq!(get_overlaps(&fabric), 4);
+        assert_eq!(fabric.overlaps, 4);
 
         assert!(!check_non_overlapping_claim(
             &fabric,
-            &Claim::parse_command("#1 @ 1,3: 4x4")
+            &Claim::parse_command("#1 @ 1,3: 4x4").unwrap()
         ));
         assert!(!check_non_overlapping_claim(
             &fabric,
-            &Claim::parse_command("#2 @ 3,1: 4x4")
+            &Claim::parse_command("#2 @ 3,1: 4x4").unwrap()
         ));
         assert!(check_non_overlapping_claim(
             &fabric,
-            &Claim::parse_command("#3 @ 5,5: 2x2")
+            &Claim::parse_command("#3 @ 5,5: 2x2").unwrap()
         ));
     }
 
+    #[test]
+    fn test_paint_claim() {
+        let mut fabric = Fabric::new(8);
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &overlap_color(0),
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        for claim_str in ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4"] {
+            let claim = Claim::parse_command(claim_str).unwrap();
+            update_fabric_wth_claim(&mut fabric, &claim);
+            paint_claim(&mut image, &fabric, &claim);
+        }
+        let pixel = |x: usize, y: usize| &image.data[(y * 8 + x) * 4..(y * 8 + x) * 4 + 4];
+        assert_eq!(pixel(0, 0), overlap_color(0));
+        assert_eq!(pixel(1, 3), overlap_color(1));
+        assert_eq!(pixel(3, 3), overlap_color(2));
+
+        let mut repainted = image.clone();
+        paint_fabric(&mut repainted, &fabric);
+        assert_eq!(repainted.data, image.data);
+    }
+
+    #[test]
+    fn test_fabric_for_input() {
+        let lines = ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"].map(String::from);
+        assert_eq!(Fabric::for_input(&lines).size(), 7);
+        assert_eq!(Fabric::for_input(&[]).size(), 1);
+    }
+
+    #[test]
+    fn test_claim_scan_without_intact_claim() {
+        let mut scan = ClaimScan::new();
+        let mut inputs = InputLines::from_lines(vec![
+            "#1 @ 1,1: 2x2".to_string(),
+            "#2 @ 2,2: 2x2".to_string(),
+        ]);
+        while scan.step(&mut inputs) {}
+        assert!(scan.exhausted);
+        assert_eq!(scan.answer(), "none");
+    }
+
     #[test]
     #[ignore]
     fn calculate_part1() {
-        let mut inputs = InputLines::from_slice(include_bytes!("../../inputs/2018/day3.txt"));
-        let mut fabric = Fabric::new(1000);
+        let mut inputs =
+            InputLines::from_slice(include_bytes!("../../assets/inputs/2018/day3.txt")).unwrap();
+        let mut fabric = Fabric::new(MAX_FABRIC_SIZE);
         while let Some(claim) = inputs.next(false) {
-            update_fabric_wth_claim(&mut fabric, Claim::parse_command(claim));
+            update_fabric_wth_claim(&mut fabric, &Claim::parse_command(claim).unwrap());
         }
 
-        println!("Answer for day3 part1 is {}", get_overlaps(&fabric));
+        println!("Answer for day3 part1 is {}", fabric.overlaps);
     }
 
     #[test]
     #[ignore]
     fn calculate_part2() {
-        let mut inputs = InputLines::from_slice(include_bytes!("../../inputs/2018/day3.txt"));
-        let mut fabric = Fabric::new(1000);
-        while let Some(claim) = inputs.next(false) {
-            update_fabric_wth_claim(&mut fabric, Claim::parse_command(claim));
-        }
-
-        inputs.refresh();
+        let mut scan = ClaimScan::new();
+        let mut inputs =
+            InputLines::from_slice(include_bytes!("../../assets/inputs/2018/day3.txt")).unwrap();
+        while scan.step(&mut inputs) {}
 
-        loop {
-            match inputs.next(false) {
-                Some(claim_str) => {
-                    let claim = Claim::parse_command(claim_str);
-                    if check_non_overlapping_claim(&fabric, &claim) {
-                        println!("Answer for day3 part2 is {}", claim.id);
-                        break;
-                    }
-                }
-                None => {
-                    println!("Answer for day3 part2 not found");
-                    break;
-                }
-            }
-        }
+        println!("Answer for day3 part2 is {}", scan.answer());
     }
 }
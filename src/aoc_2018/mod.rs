@@ -1,12 +1,16 @@
-use bevy::prelude::App;
+use crate::commons::puzzle::Puzzle;
 
 mod day1;
 mod day2;
 mod day3;
 
-pub fn setup_app(mut app: App) -> App {
-    app = day1::setup(app);
-    app = day2::setup(app);
-    app = day3::setup(app);
-    app
+pub fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(day1::Part1),
+        Box::new(day1::Part2),
+        Box::new(day2::Part1),
+        Box::new(day2::Part2),
+        Box::new(day3::Part1),
+        Box::new(day3::Part2),
+    ]
 }
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadState, LoadedAsset},
+    prelude::*,
+    reflect::{TypeUuid, Uuid},
+    utils::BoxedFuture,
+};
+
+use super::{
+    aoc_common::{AOCState, AocFont, PuzzleEntity},
+    constants::WINDOW_WIDTH,
+    event_log::EventLog,
+    puzzle_input::{InputSelection, InputSource},
+};
+
+pub struct AnswersPlugin;
+
+impl Plugin for AnswersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Answers>()
+            .init_asset_loader::<AnswersLoader>();
+    }
+}
+
+/// Expected answers of one year, read from `answers/<year>.toml`. Each input source has its
+/// own table keyed by `day<day>_part<part>`:
+///
+/// ```toml
+/// [real]
+/// day1_part1 = "442"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    sources: HashMap<String, HashMap<String, String>>,
+}
+
+// The derive macro only looks for bevy in the non target specific dependencies
+impl TypeUuid for Answers {
+    const TYPE_UUID: Uuid = Uuid::from_u128(0x0c9e2f61_7a4d_4b3e_8f15_92d6a7c4e8b1);
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let value = text
+            .parse::<toml::Value>()
+            .map_err(|error| error.to_string())?;
+        let mut sources = HashMap::new();
+        for (source, table) in value.as_table().into_iter().flatten() {
+            let table = table
+                .as_table()
+                .ok_or_else(|| format!("[{}] is not a table", source))?;
+            let answers = table
+                .iter()
+                .map(|(key, answer)| {
+                    let answer = match answer {
+                        toml::Value::String(answer) => answer.clone(),
+                        answer => answer.to_string(),
+                    };
+                    (key.clone(), answer)
+                })
+                .collect();
+            sources.insert(source.clone(), answers);
+        }
+        Ok(Self { sources })
+    }
+
+    /// Custom inputs never have a stored answer.
+    pub fn expected(&self, source: InputSource, day: u8, part: u8) -> Option<&str> {
+        let table = match source {
+            InputSource::Sample => "sample",
+            InputSource::Real => "real",
+            InputSource::Custom => return None,
+        };
+        self.sources
+            .get(table)?
+            .get(&format!("day{}_part{}", day, part))
+            .map(|answer| answer.as_str())
+    }
+}
+
+#[derive(Default)]
+pub struct AnswersLoader;
+
+impl AssetLoader for AnswersLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let answers =
+                Answers::parse(std::str::from_utf8(bytes)?).map_err(anyhow::Error::msg)?;
+            load_context.set_default_asset(LoadedAsset::new(answers));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["toml"]
+    }
+}
+
+pub fn answers_path(year: u16) -> String {
+    format!("answers/{}.toml", year)
+}
+
+/// Answer of the active puzzle, reported by its step systems once the input is exhausted.
+#[derive(Default)]
+pub struct FinalAnswer(pub Option<String>);
+
+impl FinalAnswer {
+    pub fn is_reported(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn report(&mut self, answer: impl ToString) {
+        if self.0.is_none() {
+            self.0 = Some(answer.to_string());
+        }
+    }
+}
+
+/// Answer of the active puzzle so far, shown in the HUD until the final answer is reported.
+#[derive(Default)]
+pub struct CurrentAnswer(pub Option<String>);
+
+impl CurrentAnswer {
+    pub fn update(&mut self, answer: impl ToString) {
+        self.0 = Some(answer.to_string());
+    }
+}
+
+pub struct AnswersHandle(Handle<Answers>);
+
+pub fn load_answers(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    state: Res<State<AOCState>>,
+) {
+    let handle = asset_server.load(answers_path(state.current().year).as_str());
+    commands.insert_resource(AnswersHandle(handle));
+}
+
+/// Stored answer for the active puzzle and input, once the answers file has loaded.
+pub enum ExpectedAnswer {
+    Loading,
+    Loaded(Option<String>),
+}
+
+pub fn reset_final_answer(mut commands: Commands) {
+    commands.insert_resource(FinalAnswer::default());
+    commands.insert_resource(CurrentAnswer::default());
+    commands.insert_resource(ExpectedAnswer::Loading);
+}
+
+pub fn unload_answers(mut commands: Commands) {
+    commands.remove_resource::<FinalAnswer>();
+    commands.remove_resource::<CurrentAnswer>();
+    commands.remove_resource::<ExpectedAnswer>();
+    commands.remove_resource::<AnswersHandle>();
+}
+
+pub fn lookup_expected_answer(
+    mut expected: ResMut<ExpectedAnswer>,
+    state: Res<State<AOCState>>,
+    input_selection: Res<InputSelection>,
+    handle: Res<AnswersHandle>,
+    answers: Res<Assets<Answers>>,
+    asset_server: Res<AssetServer>,
+) {
+    if let ExpectedAnswer::Loaded(_) = *expected {
+        return;
+    }
+    if let Some(answers) = answers.get(&handle.0) {
+        let puzzle = state.current();
+        let answer = answers.expected(input_selection.source, puzzle.day, puzzle.part);
+        *expected = ExpectedAnswer::Loaded(answer.map(|answer| answer.to_string()));
+    } else if asset_server.get_load_state(&handle.0) == LoadState::Failed {
+        // A year without an answers file is simply unverified
+        *expected = ExpectedAnswer::Loaded(None);
+    }
+}
+
+#[derive(Component)]
+pub struct AnswerBadge;
+
+/// Shows whether the final answer matches the stored one once the puzzle reports it.
+pub fn show_answer_badge(
+    mut commands: Commands,
+    final_answer: Res<FinalAnswer>,
+    expected: Res<ExpectedAnswer>,
+    aoc_font: Res<AocFont>,
+    mut event_log: ResMut<EventLog>,
+    query: Query<Entity, With<AnswerBadge>>,
+) {
+    let (answer, expected) = match (&final_answer.0, &*expected) {
+        (Some(answer), ExpectedAnswer::Loaded(expected)) if query.is_empty() => (answer, expected),
+        _ => return,
+    };
+    let (text, color) = match expected {
+        Some(expected) if expected == answer => (format!("Correct: {}", answer), Color::GREEN),
+        Some(expected) => (
+            format!("Wrong: {} (expected {})", answer, expected),
+            Color::RED,
+        ),
+        None => (format!("Unverified: {}", answer), Color::GRAY),
+    };
+    if matches!(expected, Some(expected) if expected != answer) {
+        event_log.warn(None, text.clone());
+    } else {
+        event_log.push(None, text.clone());
+    }
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(WINDOW_WIDTH / 2.0 - 150.0),
+                    top: Val::Px(10.0),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(10.0)),
+                ..Default::default()
+            },
+            color: color.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    text,
+                    TextStyle {
+                        font: aoc_font.font_handle.clone(),
+                        font_size: 30.0,
+                        color: Color::BLACK,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+        })
+        .insert(AnswerBadge)
+        .insert(PuzzleEntity);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers =
+            Answers::parse("[sample]\nday1_part1 = 3\n\n[real]\n# comment\nday1_part1 = \"442\"\n")
+                .unwrap();
+        assert_eq!(answers.expected(InputSource::Sample, 1, 1), Some("3"));
+        assert_eq!(answers.expected(InputSource::Real, 1, 1), Some("442"));
+        assert_eq!(answers.expected(InputSource::Real, 1, 2), None);
+        assert_eq!(answers.expected(InputSource::Custom, 1, 1), None);
+        assert!(Answers::parse("day1_part1 = 3").is_err());
+    }
+}
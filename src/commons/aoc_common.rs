@@ -1,6 +1,3 @@
-use std::io::BufRead;
-use std::io::BufReader;
-
 use bevy::prelude::*;
 
 use super::constants;
@@ -12,54 +9,324 @@ pub struct AOCState {
     pub part: u8,
 }
 
+impl AOCState {
+    pub fn menu() -> Self {
+        Self {
+            year: 0,
+            day: 0,
+            part: 1,
+        }
+    }
+
+    /// Transient state pushed on top of a puzzle to run its cleanup and setup again.
+    pub fn restart() -> Self {
+        Self {
+            year: 0,
+            day: 0,
+            part: 0,
+        }
+    }
+}
+
 pub struct BorderSize {
     pub max_x: f32,
     pub max_y: f32,
     pub current_x: f32,
     pub current_y: f32,
+    /// Centre of the content the current size is measured around.
+    pub center: Vec2,
 }
 
+impl BorderSize {
+    pub fn reset(&mut self) {
+        self.current_x = 0.0;
+        self.current_y = 0.0;
+        self.center = Vec2::ZERO;
+    }
+}
+
+/// Marks sprites and texts whose bounds the camera keeps in view while following.
 #[derive(Component)]
 pub struct ScalableObject;
 
 #[derive(Component)]
 pub struct AOCName(pub String);
 
+/// Marks every entity spawned by a puzzle so it can be despawned when the puzzle is left.
 #[derive(Component)]
+pub struct PuzzleEntity;
+
+/// Puzzle input consumed one line at a time. Puzzles spawn it empty and it is filled in
+/// once the puzzle's input asset has loaded, see [`super::puzzle_input`]. Lines are already
+/// normalised by the puzzle's [`super::parse::LineSplitter`], and can be viewed as a [`InputGrid`],
+/// [`InputGroups`] or [`InputTokens`] for puzzles that do not read line by line.
+#[derive(Component, Default)]
 pub struct InputLines {
     pub input_lines: Vec<String>,
     pub read_pos: usize,
+    pub loaded: bool,
+    wraps: usize,
 }
 
 impl InputLines {
-    pub fn from_slice(input_slice: &[u8]) -> Self {
-        let input_lines = BufReader::new(input_slice)
-            .lines()
-            .map(|line| line.unwrap())
-            .collect::<Vec<String>>();
-        let read_pos = 0;
+    /// Reads inputs embedded with `include_bytes!` in tests.
+    #[cfg(test)]
+    pub fn from_slice(input_slice: &[u8]) -> Result<Self, super::parse::ParseError> {
+        super::parse::split_lines(input_slice).map(Self::from_lines)
+    }
+
+    pub fn from_lines(input_lines: Vec<String>) -> Self {
         Self {
             input_lines,
-            read_pos,
+            loaded: true,
+            ..Default::default()
         }
     }
 
+    /// Next line, starting over from the first one once the end is reached if `repeat` is set.
     pub fn next(&mut self, repeat: bool) -> Option<&String> {
-        let mut pos = self.read_pos;
-        if repeat {
-            pos = self.read_pos % self.input_lines.len();
+        let pos = next_index(
+            &mut self.read_pos,
+            &mut self.wraps,
+            self.input_lines.len(),
+            repeat,
+        )?;
+        Some(&self.input_lines[pos])
+    }
+
+    /// How many times `next(true)` started over from the first line.
+    pub fn wraps(&self) -> usize {
+        self.wraps
+    }
+
+    pub fn refresh(&mut self) {
+        self.read_pos = 0;
+        self.wraps = 0;
+    }
+}
+
+// The views are for puzzles that are not read line by line, which no day so far is.
+#[allow(dead_code)]
+impl InputLines {
+    /// The whole input as one string, for puzzles given a single long line.
+    pub fn text(&self) -> String {
+        self.input_lines.join("\n")
+    }
+
+    pub fn grid(&self) -> InputGrid {
+        InputGrid::from_lines(&self.input_lines)
+    }
+
+    pub fn groups(&self) -> InputGroups {
+        InputGroups::from_lines(&self.input_lines)
+    }
+
+    pub fn tokens(&self, separator: char) -> InputTokens {
+        InputTokens::from_lines(&self.input_lines, separator)
+    }
+}
+
+/// Advances a read cursor over `len` items, returning the index to read. With `repeat` the
+/// cursor keeps counting past the end and `wraps` counts each time it starts over.
+fn next_index(read_pos: &mut usize, wraps: &mut usize, len: usize, repeat: bool) -> Option<usize> {
+    let mut pos = *read_pos;
+    if repeat && len > 0 {
+        pos = *read_pos % len;
+        if pos == 0 && *read_pos > 0 {
+            *wraps += 1;
+        }
+    }
+    if pos < len {
+        *read_pos += 1;
+        Some(pos)
+    } else {
+        None
+    }
+}
+
+/// Input as a 2D character grid, read cell by cell in rows from the top left. Shorter rows
+/// are not padded, cells past their end are simply missing.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct InputGrid {
+    pub rows: Vec<Vec<char>>,
+    pub read_pos: usize,
+    wraps: usize,
+    /// Index of the first cell of each row, counting cells from the top left.
+    row_starts: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl InputGrid {
+    pub fn from_lines(lines: &[String]) -> Self {
+        let rows = lines
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect::<Vec<Vec<char>>>();
+        let row_starts = rows
+            .iter()
+            .scan(0, |start, row| {
+                let row_start = *start;
+                *start += row.len();
+                Some(row_start)
+            })
+            .collect();
+        Self {
+            rows,
+            row_starts,
+            ..Default::default()
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.rows.get(y).and_then(|row| row.get(x)).copied()
+    }
+
+    /// Next cell as `((x, y), char)`.
+    pub fn next(&mut self, repeat: bool) -> Option<((usize, usize), char)> {
+        let len = self.cell_count();
+        let pos = next_index(&mut self.read_pos, &mut self.wraps, len, repeat)?;
+        let (x, y) = self.position(pos);
+        Some(((x, y), self.rows[y][x]))
+    }
+
+    /// Position of the cell read last, to highlight it.
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        let len = self.cell_count();
+        if self.read_pos == 0 || len == 0 {
+            return None;
+        }
+        Some(self.position((self.read_pos - 1) % len))
+    }
+
+    pub fn wraps(&self) -> usize {
+        self.wraps
+    }
+
+    pub fn refresh(&mut self) {
+        self.read_pos = 0;
+        self.wraps = 0;
+    }
+
+    fn cell_count(&self) -> usize {
+        match (self.row_starts.last(), self.rows.last()) {
+            (Some(start), Some(row)) => start + row.len(),
+            _ => 0,
         }
-        if pos < self.input_lines.len() {
-            let value = &self.input_lines[pos];
-            self.read_pos += 1;
-            Some(value)
-        } else {
-            None
+    }
+
+    /// Position of a cell by its index, which must be below the cell count. Empty rows
+    /// share their start with the next row, so the last row starting at or before the
+    /// index is the one holding it.
+    fn position(&self, index: usize) -> (usize, usize) {
+        let y = self.row_starts.partition_point(|start| *start <= index) - 1;
+        (index - self.row_starts[y], y)
+    }
+}
+
+/// Input made of blocks of lines separated by blank lines, read a group at a time.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct InputGroups {
+    pub groups: Vec<Vec<String>>,
+    pub read_pos: usize,
+    wraps: usize,
+}
+
+#[allow(dead_code)]
+impl InputGroups {
+    pub fn from_lines(lines: &[String]) -> Self {
+        let groups = lines
+            .split(|line| line.is_empty())
+            .filter(|group| !group.is_empty())
+            .map(|group| group.to_vec())
+            .collect();
+        Self {
+            groups,
+            ..Default::default()
+        }
+    }
+
+    pub fn next(&mut self, repeat: bool) -> Option<&[String]> {
+        let pos = next_index(
+            &mut self.read_pos,
+            &mut self.wraps,
+            self.groups.len(),
+            repeat,
+        )?;
+        Some(&self.groups[pos])
+    }
+
+    pub fn wraps(&self) -> usize {
+        self.wraps
+    }
+
+    pub fn refresh(&mut self) {
+        self.read_pos = 0;
+        self.wraps = 0;
+    }
+}
+
+/// Input split on a separator, such as comma separated numbers, read a token at a time.
+/// Tokens are trimmed and the line breaks count as separators too.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct InputTokens {
+    pub tokens: Vec<String>,
+    pub read_pos: usize,
+    wraps: usize,
+}
+
+#[allow(dead_code)]
+impl InputTokens {
+    pub fn from_lines(lines: &[String], separator: char) -> Self {
+        let tokens = lines
+            .iter()
+            .flat_map(|line| line.split(separator))
+            .map(|token| token.trim())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.to_string())
+            .collect();
+        Self {
+            tokens,
+            ..Default::default()
         }
     }
 
+    pub fn next(&mut self, repeat: bool) -> Option<&str> {
+        let pos = next_index(
+            &mut self.read_pos,
+            &mut self.wraps,
+            self.tokens.len(),
+            repeat,
+        )?;
+        Some(&self.tokens[pos])
+    }
+
+    /// Parses every token, for example into the numbers of a comma separated list.
+    pub fn parse<T: std::str::FromStr>(&self) -> Result<Vec<T>, String> {
+        self.tokens
+            .iter()
+            .map(|token| {
+                token
+                    .parse()
+                    .map_err(|_| format!("could not parse token {:?}", token))
+            })
+            .collect()
+    }
+
+    pub fn wraps(&self) -> usize {
+        self.wraps
+    }
+
     pub fn refresh(&mut self) {
         self.read_pos = 0;
+        self.wraps = 0;
     }
 }
 
@@ -74,3 +341,65 @@ impl AocFont {
         });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_input_lines_wraps() {
+        let mut input = InputLines::from_lines(lines("a\nb"));
+        let read = (0..5)
+            .map(|_| input.next(true).unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(read, ["a", "b", "a", "b", "a"]);
+        assert_eq!(input.wraps(), 2);
+        input.refresh();
+        assert_eq!(input.wraps(), 0);
+        assert_eq!(input.next(false).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_input_grid() {
+        let mut grid = InputLines::from_lines(lines("#.\n.#.")).grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 1), Some('#'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.cursor(), None);
+        grid.next(false);
+        grid.next(false);
+        assert_eq!(grid.next(false), Some(((0, 1), '.')));
+        assert_eq!(grid.cursor(), Some((0, 1)));
+
+        let mut grid = InputLines::from_lines(lines("\nab\n\nc\n")).grid();
+        let read = (0..4).map(|_| grid.next(true).unwrap()).collect::<Vec<_>>();
+        assert_eq!(
+            read,
+            [((0, 1), 'a'), ((1, 1), 'b'), ((0, 3), 'c'), ((0, 1), 'a')]
+        );
+        assert_eq!(grid.cursor(), Some((0, 1)));
+        assert_eq!(grid.wraps(), 1);
+    }
+
+    #[test]
+    fn test_input_groups() {
+        let mut groups = InputLines::from_lines(lines("a\nb\n\n\nc")).groups();
+        assert_eq!(groups.next(false).unwrap(), ["a", "b"]);
+        assert_eq!(groups.next(true).unwrap(), ["c"]);
+        assert_eq!(groups.next(false), None);
+        assert_eq!(groups.next(true).unwrap(), ["a", "b"]);
+        assert_eq!(groups.wraps(), 1);
+    }
+
+    #[test]
+    fn test_input_tokens() {
+        let mut tokens = InputLines::from_lines(lines("3, 4,\n-5")).tokens(',');
+        assert_eq!(tokens.parse::<i32>().unwrap(), [3, 4, -5]);
+        assert_eq!(tokens.next(false), Some("3"));
+        assert!(tokens.parse::<u8>().is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+
+use super::{
+    aoc_common::{AocFont, PuzzleEntity},
+    scale::{format_tick, Scale},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AxisOrientation {
+    /// Ticks along x, labelled below the axis line.
+    Horizontal,
+    /// Ticks along y, labelled left of the axis line.
+    Vertical,
+}
+
+/// Axis line with ticks and labels for a chart. The ticks are moved in place whenever the
+/// component changes, so only assign a new scale when it differs from the current one.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct Axis {
+    pub orientation: AxisOrientation,
+    pub scale: Scale,
+    /// Position of the axis line across its orientation, y for horizontal axes.
+    pub position: f32,
+    pub ticks: usize,
+}
+
+impl Axis {
+    pub fn horizontal(scale: Scale, y: f32) -> Self {
+        Self {
+            orientation: AxisOrientation::Horizontal,
+            scale,
+            position: y,
+            ticks: 8,
+        }
+    }
+
+    pub fn vertical(scale: Scale, x: f32) -> Self {
+        Self {
+            orientation: AxisOrientation::Vertical,
+            scale,
+            position: x,
+            ticks: 6,
+        }
+    }
+}
+
+/// Updates the scale of an axis without flagging a change when it is the same.
+pub fn set_axis_scale(axis: &mut Mut<Axis>, scale: Scale) {
+    if axis.scale != scale {
+        axis.scale = scale;
+    }
+}
+
+pub fn spawn_axis(commands: &mut Commands, axis: Axis) -> Entity {
+    commands
+        .spawn_bundle((Transform::identity(), GlobalTransform::identity()))
+        .insert(axis)
+        .insert(PuzzleEntity)
+        .id()
+}
+
+const AXIS_COLOR: Color = Color::rgb(0.6, 0.6, 0.6);
+const TICK_LENGTH: f32 = 6.0;
+
+/// Child entity drawing part of an axis. Ticks and labels are reused when the scale changes,
+/// the ones past the current number of ticks are hidden.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum AxisPart {
+    Line,
+    Tick(usize),
+    Label(usize),
+}
+
+/// Where a tick and its label go, relative to the axis entity.
+struct TickLayout {
+    tick_position: Vec3,
+    tick_size: Vec2,
+    label_position: Vec3,
+    label: String,
+}
+
+impl Axis {
+    fn line_layout(&self) -> (Vec3, Vec2) {
+        let (start, end) = self.scale.range;
+        let length = (end - start).abs();
+        let middle = (start + end) / 2.0;
+        match self.orientation {
+            AxisOrientation::Horizontal => (
+                Vec3::new(middle, self.position, 1.0),
+                Vec2::new(length, 1.0),
+            ),
+            AxisOrientation::Vertical => (
+                Vec3::new(self.position, middle, 1.0),
+                Vec2::new(1.0, length),
+            ),
+        }
+    }
+
+    fn tick_layouts(&self) -> Vec<TickLayout> {
+        self.scale
+            .ticks(self.ticks)
+            .into_iter()
+            .map(|tick| {
+                let at = self.scale.map(tick);
+                let (tick_position, tick_size, label_position) = match self.orientation {
+                    AxisOrientation::Horizontal => (
+                        Vec3::new(at, self.position - TICK_LENGTH / 2.0, 1.0),
+                        Vec2::new(1.0, TICK_LENGTH),
+                        Vec3::new(at, self.position - TICK_LENGTH - 2.0, 1.0),
+                    ),
+                    AxisOrientation::Vertical => (
+                        Vec3::new(self.position - TICK_LENGTH / 2.0, at, 1.0),
+                        Vec2::new(TICK_LENGTH, 1.0),
+                        Vec3::new(self.position - TICK_LENGTH - 4.0, at, 1.0),
+                    ),
+                };
+                TickLayout {
+                    tick_position,
+                    tick_size,
+                    label_position,
+                    label: format_tick(tick),
+                }
+            })
+            .collect()
+    }
+
+    fn label_alignment(&self) -> TextAlignment {
+        match self.orientation {
+            AxisOrientation::Horizontal => TextAlignment {
+                vertical: VerticalAlign::Top,
+                horizontal: HorizontalAlign::Center,
+            },
+            AxisOrientation::Vertical => TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Right,
+            },
+        }
+    }
+}
+
+type AxisPartQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static AxisPart,
+        &'static mut Transform,
+        &'static mut Visibility,
+        Option<&'static mut Sprite>,
+        Option<&'static mut Text>,
+    ),
+>;
+
+pub fn draw_axes(
+    mut commands: Commands,
+    aoc_font: Res<AocFont>,
+    axes: Query<(Entity, &Axis, Option<&Children>), Changed<Axis>>,
+    mut parts: AxisPartQuery,
+) {
+    for (entity, axis, children) in axes.iter() {
+        let (line_position, line_size) = axis.line_layout();
+        let layouts = axis.tick_layouts();
+        let mut has_line = false;
+        let mut ticks = 0;
+        for child in children.into_iter().flat_map(|children| children.iter()) {
+            let (part, mut transform, mut visibility, sprite, text) = match parts.get_mut(*child) {
+                Ok(part) => part,
+                Err(_) => continue,
+            };
+            match (*part, sprite, text) {
+                (AxisPart::Line, Some(mut sprite), _) => {
+                    has_line = true;
+                    transform.translation = line_position;
+                    sprite.custom_size = Some(line_size);
+                }
+                (AxisPart::Tick(index), Some(mut sprite), _) => {
+                    ticks = ticks.max(index + 1);
+                    visibility.is_visible = index < layouts.len();
+                    if let Some(layout) = layouts.get(index) {
+                        transform.translation = layout.tick_position;
+                        sprite.custom_size = Some(layout.tick_size);
+                    }
+                }
+                (AxisPart::Label(index), _, Some(mut text)) => {
+                    visibility.is_visible = index < layouts.len();
+                    if let Some(layout) = layouts.get(index) {
+                        transform.translation = layout.label_position;
+                        text.sections[0].value = layout.label.clone();
+                    }
+                }
+                _ => {}
+            }
+        }
+        let text_style = TextStyle {
+            font: aoc_font.font_handle.clone(),
+            font_size: 16.0,
+            color: AXIS_COLOR,
+        };
+        commands.entity(entity).with_children(|parent| {
+            if !has_line {
+                parent
+                    .spawn_bundle(line_sprite(line_position, line_size))
+                    .insert(AxisPart::Line);
+            }
+            for (index, layout) in layouts.iter().enumerate().skip(ticks) {
+                parent
+                    .spawn_bundle(line_sprite(layout.tick_position, layout.tick_size))
+                    .insert(AxisPart::Tick(index));
+                parent
+                    .spawn_bundle(Text2dBundle {
+                        text: Text::with_section(
+                            layout.label.clone(),
+                            text_style.clone(),
+                            axis.label_alignment(),
+                        ),
+                        transform: Transform::from_translation(layout.label_position),
+                        ..Default::default()
+                    })
+                    .insert(AxisPart::Label(index));
+            }
+        });
+    }
+}
+
+fn line_sprite(position: Vec3, size: Vec2) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            color: AXIS_COLOR,
+            custom_size: Some(size),
+            ..Default::default()
+        },
+        transform: Transform::from_translation(position),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    // Also in the bevy prelude
+    use super::Axis;
+
+    fn labels(app: &mut App) -> Vec<(Entity, String, bool)> {
+        let mut query = app.world.query::<(Entity, &AxisPart, &Text, &Visibility)>();
+        let mut labels = query
+            .iter(&app.world)
+            .filter_map(|(entity, part, text, visibility)| match part {
+                AxisPart::Label(index) => Some((
+                    *index,
+                    entity,
+                    text.sections[0].value.clone(),
+                    visibility.is_visible,
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        labels.sort_by_key(|(index, ..)| *index);
+        labels
+            .into_iter()
+            .map(|(_, entity, label, visible)| (entity, label, visible))
+            .collect()
+    }
+
+    #[test]
+    fn test_ticks_move_in_place() {
+        let mut app = App::new();
+        app.insert_resource(AocFont {
+            font_handle: Handle::default(),
+        })
+        .add_system(draw_axes);
+        let axis = Axis::horizontal(Scale::linear((0.0, 10.0), (0.0, 100.0)), 0.0);
+        let entity = app.world.spawn().insert(axis).id();
+        app.update();
+        let before = labels(&mut app);
+        assert_eq!(before.len(), 6);
+        assert_eq!(before[1].1, "2");
+
+        let scale = Scale::linear((0.0, 20.0), (0.0, 100.0));
+        let mut axis = app.world.get_mut::<Axis>(entity).unwrap();
+        set_axis_scale(&mut axis, scale);
+        app.update();
+        let after = labels(&mut app);
+        assert_eq!(after.len(), 6);
+        assert_eq!(after[0].0, before[0].0);
+        assert_eq!(after[1].1, "5");
+        assert!(after[4].2);
+        assert!(!after[5].2);
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+
+use super::{
+    aoc_common::{AocFont, PuzzleEntity, ScalableObject},
+    scale::{format_tick, Scale},
+};
+
+/// Bar chart drawn upwards from the chart entity's translation. Bars are children of the
+/// chart, ordered left to right by their index.
+#[derive(Component)]
+pub struct BarChart {
+    pub height: f32,
+    /// Bar heights follow a symmetric log scale, for values of very different sizes.
+    pub log_scale: bool,
+}
+
+impl BarChart {
+    fn scale(&self, max: f32) -> Scale {
+        let domain = (0.0, max.max(1.0));
+        if self.log_scale {
+            Scale::symlog(domain, (0.0, self.height))
+        } else {
+            Scale::linear(domain, (0.0, self.height))
+        }
+    }
+}
+
+/// A bar growing smoothly towards `value`.
+#[derive(Component)]
+pub struct Bar {
+    pub index: usize,
+    pub value: f32,
+    pub color: Color,
+    shown: f32,
+}
+
+#[derive(Component)]
+pub enum BarLabel {
+    Name,
+    Value,
+}
+
+/// Layout and labels of the bars of a new chart.
+pub struct BarChartSpec<'a> {
+    pub origin: Vec2,
+    pub height: f32,
+    pub bar_width: f32,
+    pub gap: f32,
+    pub font_size: f32,
+    pub log_scale: bool,
+    pub bars: Vec<(&'a str, Color)>,
+}
+
+pub fn spawn_bar_chart(commands: &mut Commands, aoc_font: &AocFont, spec: BarChartSpec) -> Entity {
+    let text_style = TextStyle {
+        font: aoc_font.font_handle.clone(),
+        font_size: spec.font_size,
+        color: Color::WHITE,
+    };
+    let label_alignment = TextAlignment {
+        vertical: VerticalAlign::Center,
+        horizontal: HorizontalAlign::Center,
+    };
+    commands
+        .spawn_bundle((
+            Transform::from_translation(spec.origin.extend(0.0)),
+            GlobalTransform::identity(),
+        ))
+        .insert(BarChart {
+            height: spec.height,
+            log_scale: spec.log_scale,
+        })
+        .insert(PuzzleEntity)
+        .with_children(|parent| {
+            for (index, (name, color)) in spec.bars.into_iter().enumerate() {
+                let x = spec.bar_width / 2.0 + index as f32 * (spec.bar_width + spec.gap);
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::new(spec.bar_width, 0.0)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(x, 0.0, 0.0),
+                        ..Default::default()
+                    })
+                    .insert(Bar {
+                        index,
+                        value: 0.0,
+                        color,
+                        shown: 0.0,
+                    })
+                    .insert(ScalableObject)
+                    .with_children(|bar| {
+                        bar.spawn_bundle(Text2dBundle {
+                            text: Text::with_section(name, text_style.clone(), label_alignment),
+                            ..Default::default()
+                        })
+                        .insert(BarLabel::Name);
+                        bar.spawn_bundle(Text2dBundle {
+                            text: Text::with_section("0", text_style.clone(), label_alignment),
+                            ..Default::default()
+                        })
+                        .insert(BarLabel::Value);
+                    });
+            }
+        })
+        .id()
+}
+
+/// Fraction of the remaining distance a bar covers per second.
+const TWEEN_RATE: f32 = 8.0;
+
+type BarQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Bar,
+        &'static mut Sprite,
+        &'static mut Transform,
+        &'static Children,
+    ),
+>;
+type BarLabelQuery<'w, 's> =
+    Query<'w, 's, (&'static BarLabel, &'static mut Transform, &'static mut Text), Without<Bar>>;
+
+/// Moves `blend` of the way from `shown` to `value`, snapping once close enough.
+fn ease(shown: f32, value: f32, blend: f32) -> f32 {
+    if (value - shown).abs() < 0.01 {
+        value
+    } else {
+        shown + (value - shown) * blend
+    }
+}
+
+/// Eases every bar towards its value and keeps its labels above and below it.
+pub fn tween_bars(
+    time: Res<Time>,
+    charts: Query<(&BarChart, &Children)>,
+    mut bars: BarQuery,
+    mut labels: BarLabelQuery,
+) {
+    let blend = 1.0 - (-TWEEN_RATE * time.delta_seconds()).exp();
+    for (chart, children) in charts.iter() {
+        let max = children
+            .iter()
+            .filter_map(|child| bars.get(*child).ok())
+            .map(|(bar, ..)| bar.value)
+            .fold(0.0, f32::max);
+        let scale = chart.scale(max);
+        for child in children.iter() {
+            let (mut bar, mut sprite, mut transform, bar_children) = match bars.get_mut(*child) {
+                Ok(bar) => bar,
+                Err(_) => continue,
+            };
+            let shown = ease(bar.shown, bar.value, blend);
+            bar.shown = shown;
+            let height = scale.map(shown);
+            sprite.color = bar.color;
+            if let Some(size) = sprite.custom_size.as_mut() {
+                size.y = height;
+            }
+            transform.translation.y = height / 2.0;
+            for label in bar_children.iter() {
+                if let Ok((kind, mut label_transform, mut text)) = labels.get_mut(*label) {
+                    match kind {
+                        BarLabel::Name => label_transform.translation.y = -height / 2.0 - 15.0,
+                        BarLabel::Value => {
+                            label_transform.translation.y = height / 2.0 + 15.0;
+                            text.sections[0].value = format_tick(shown.round());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn toggle_log_scale(keyboard_input: Res<Input<KeyCode>>, mut charts: Query<&mut BarChart>) {
+    if keyboard_input.just_pressed(KeyCode::L) {
+        for mut chart in charts.iter_mut() {
+            chart.log_scale = !chart.log_scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ease() {
+        assert_eq!(ease(0.0, 10.0, 0.5), 5.0);
+        assert_eq!(ease(10.0, 0.0, 0.25), 7.5);
+        assert_eq!(ease(9.995, 10.0, 0.5), 10.0);
+    }
+}
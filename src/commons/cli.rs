@@ -0,0 +1,190 @@
+use super::aoc_common::AOCState;
+
+/// Options read from the command line. The wasm build reads the puzzle and speed options
+/// from the page's query string, `?year=2018&day=3&part=1` is read as
+/// `--year 2018 --day 3 --part 1`.
+#[derive(Default, Debug, PartialEq)]
+pub struct CliArgs {
+    /// Input file used instead of the puzzle's own input.
+    pub input: Option<String>,
+    /// Puzzle to solve without opening a window, given as `--solve <year> <day> <part>`.
+    pub solve: Option<AOCState>,
+    /// Puzzle opened instead of the menu, given as `--year <year> --day <day> [--part <part>]`.
+    pub start: Option<AOCState>,
+    /// Steps per second puzzles start at instead of their own default.
+    pub speed: Option<f32>,
+}
+
+impl CliArgs {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn from_env() -> Result<Self, String> {
+        let query = web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .unwrap_or_default();
+        Self::parse(query_args(&query).into_iter())
+    }
+
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut cli_args = Self::default();
+        let (mut year, mut day, mut part) = (None, None, None);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = next_value(&mut args, &arg)?;
+                    cli_args.input = Some(absolute_path(&path));
+                }
+                "--solve" => {
+                    cli_args.solve = Some(AOCState {
+                        year: parse_value(&mut args, &arg)?,
+                        day: parse_value(&mut args, &arg)?,
+                        part: parse_value(&mut args, &arg)?,
+                    });
+                }
+                "--year" => year = Some(parse_value(&mut args, &arg)?),
+                "--day" => day = Some(parse_value(&mut args, &arg)?),
+                "--part" => part = Some(parse_value(&mut args, &arg)?),
+                "--speed" => {
+                    let speed: f32 = parse_value(&mut args, &arg)?;
+                    if speed.is_nan() || speed <= 0.0 {
+                        return Err(format!("invalid value for {}: {}", arg, speed));
+                    }
+                    cli_args.speed = Some(speed);
+                }
+                // On stderr, the output of `--solve` is read from stdout
+                _ => eprintln!("ignoring unknown argument: {}", arg),
+            }
+        }
+        cli_args.start = match (year, day, part) {
+            (None, None, None) => None,
+            (Some(year), Some(day), part) => Some(AOCState {
+                year,
+                day,
+                part: part.unwrap_or(1),
+            }),
+            _ => return Err("--year and --day are both needed to open a puzzle".to_string()),
+        };
+        Ok(cli_args)
+    }
+}
+
+/// Options that can be given in the query string, the others have no meaning on a page.
+const QUERY_KEYS: [&str; 4] = ["year", "day", "part", "speed"];
+
+/// Turns a URL query string into command line arguments, `?day=3&part=1` into
+/// `--day 3 --part 1`. Keys other than [`QUERY_KEYS`] are skipped, pages can carry their
+/// own parameters.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn query_args(query: &str) -> Vec<String> {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            QUERY_KEYS
+                .contains(&key)
+                .then(|| [format!("--{}", key), value.to_string()])
+        })
+        .flatten()
+        .collect()
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, arg: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("missing value for {}", arg))
+}
+
+fn parse_value<T: std::str::FromStr>(
+    args: &mut impl Iterator<Item = String>,
+    arg: &str,
+) -> Result<T, String> {
+    let value = next_value(args, arg)?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", arg, value))
+}
+
+/// The asset server resolves relative paths against the assets folder, so paths given on
+/// the command line are made relative to the working directory instead.
+fn absolute_path(path: &str) -> String {
+    match std::env::current_dir() {
+        Ok(dir) => dir.join(path).to_string_lossy().into_owned(),
+        Err(_) => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse(&[]).unwrap(), CliArgs::default());
+        let cli_args = parse(&["--input", "/tmp/day1.txt"]).unwrap();
+        assert_eq!(cli_args.input.unwrap(), "/tmp/day1.txt");
+        assert!(parse(&["--input"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_solve() {
+        let cli_args = parse(&["--solve", "2018", "3", "2"]).unwrap();
+        assert_eq!(
+            cli_args.solve.unwrap(),
+            AOCState {
+                year: 2018,
+                day: 3,
+                part: 2,
+            }
+        );
+        assert!(parse(&["--solve", "2018", "three", "2"]).is_err());
+        assert!(parse(&["--solve", "2018", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_start() {
+        let cli_args = parse(&["--day", "3", "--year", "2018", "--speed", "120"]).unwrap();
+        assert_eq!(
+            cli_args.start.unwrap(),
+            AOCState {
+                year: 2018,
+                day: 3,
+                part: 1,
+            }
+        );
+        assert_eq!(cli_args.speed, Some(120.0));
+        assert_eq!(
+            parse(&["--year", "2018", "--day", "1", "--part", "2"])
+                .unwrap()
+                .start
+                .unwrap()
+                .part,
+            2
+        );
+        assert!(parse(&["--day", "3"]).is_err());
+        assert!(parse(&["--speed", "0"]).is_err());
+        assert!(parse(&["--speed", "NaN"]).is_err());
+    }
+
+    #[test]
+    fn test_query_args() {
+        assert_eq!(
+            query_args("?year=2018&day=3&part=1"),
+            ["--year", "2018", "--day", "3", "--part", "1"]
+        );
+        assert!(query_args("").is_empty());
+        assert_eq!(
+            query_args("?input=/etc/passwd&solve=2018&utm_source=x&speed=30"),
+            ["--speed", "30"]
+        );
+        assert_eq!(query_args("?day"), ["--day", ""]);
+        assert!(CliArgs::parse(query_args("?day").into_iter()).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use bevy::prelude::Color;
+
 pub const WINDOW_WIDTH: f32 = 1280.0;
 pub const WINDOW_HEIGHT: f32 = 720.0;
 pub const YEARS: [u16; 1] = [2018];
@@ -5,4 +7,11 @@ pub const DAYS: [u8; 25] = [
     1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
 ];
 pub const PARTS: [u8; 2] = [1, 2];
-pub const FONT_PATH: &'static str = "fonts/FiraSans-Bold.ttf";
+pub const FONT_PATH: &str = "fonts/FiraSans-Bold.ttf";
+pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+pub const SELECTED_BUTTON: Color = Color::rgb(0.2, 0.45, 0.2);
+pub const DISABLED_BUTTON: Color = Color::rgb(0.08, 0.08, 0.08);
+pub const NORMAL_TEXT: Color = Color::rgb(0.9, 0.9, 0.9);
+pub const DISABLED_TEXT: Color = Color::rgb(0.4, 0.4, 0.4);
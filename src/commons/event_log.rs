@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::FocusPolicy,
+};
+
+use super::{
+    aoc_common::{AocFont, PuzzleEntity},
+    constants::NORMAL_TEXT,
+};
+
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventLog>()
+            .add_system(scroll_event_log)
+            .add_system(update_event_log_panel);
+    }
+}
+
+/// Entries kept, older ones are dropped.
+const MAX_ENTRIES: usize = 200;
+/// Entries shown at once, the others are reached by scrolling.
+const VISIBLE_ENTRIES: usize = 8;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    /// Input lines read when the event happened, if it belongs to a step.
+    pub step: Option<u64>,
+    pub message: String,
+    pub warning: bool,
+}
+
+impl LogEntry {
+    fn line(&self) -> String {
+        match self.step {
+            Some(step) => format!("[step {}] {}", step, self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Notable events of the active puzzle. Every event also goes to the Bevy log, so it shows on
+/// the console natively and in the browser console for the wasm build.
+#[derive(Default)]
+pub struct EventLog {
+    entries: VecDeque<LogEntry>,
+    /// Entries scrolled back from the newest one.
+    scroll: usize,
+}
+
+impl EventLog {
+    pub fn push(&mut self, step: Option<u64>, message: impl Into<String>) {
+        let message = message.into();
+        match step {
+            Some(step) => info!(step, "{}", message),
+            None => info!("{}", message),
+        }
+        self.add(LogEntry {
+            step,
+            message,
+            warning: false,
+        });
+    }
+
+    pub fn warn(&mut self, step: Option<u64>, message: impl Into<String>) {
+        let message = message.into();
+        match step {
+            Some(step) => warn!(step, "{}", message),
+            None => warn!("{}", message),
+        }
+        self.add(LogEntry {
+            step,
+            message,
+            warning: true,
+        });
+    }
+
+    fn add(&mut self, entry: LogEntry) {
+        self.entries.push_back(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        // Stay on the same entries while scrolled back
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.max_scroll());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.scroll = 0;
+    }
+
+    /// Scrolls back towards older entries for positive `lines`.
+    pub fn scroll(&mut self, lines: isize) {
+        let scroll = self.scroll as isize + lines;
+        self.scroll = scroll.clamp(0, self.max_scroll() as isize) as usize;
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.entries.len().saturating_sub(VISIBLE_ENTRIES)
+    }
+
+    /// Entries in view, oldest first.
+    pub fn visible(&self) -> impl Iterator<Item = &LogEntry> {
+        let end = self.entries.len() - self.scroll;
+        self.entries.range(end.saturating_sub(VISIBLE_ENTRIES)..end)
+    }
+}
+
+#[derive(Component)]
+pub struct EventLogPanel;
+
+#[derive(Component)]
+pub struct EventLogText;
+
+pub fn clear_event_log(mut event_log: ResMut<EventLog>) {
+    event_log.clear();
+}
+
+/// Spawns the log panel in the bottom right, scrolled with the mouse wheel over it or with
+/// Page Up and Page Down.
+pub fn setup_event_log(
+    mut commands: Commands,
+    aoc_font: Res<AocFont>,
+    mut event_log: ResMut<EventLog>,
+) {
+    // Fills the new panel straight away
+    event_log.set_changed();
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(10.0),
+                    bottom: Val::Px(60.0),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Px(460.0), Val::Auto),
+                padding: Rect::all(Val::Px(6.0)),
+                ..Default::default()
+            },
+            color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..Default::default()
+        })
+        .insert(Interaction::default())
+        .insert(FocusPolicy::Block)
+        .insert(EventLogPanel)
+        .insert(PuzzleEntity)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: aoc_font.font_handle.clone(),
+                            font_size: 18.0,
+                            color: NORMAL_TEXT,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(EventLogText);
+        });
+}
+
+fn scroll_event_log(
+    keys: Res<Input<KeyCode>>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut event_log: ResMut<EventLog>,
+    panels: Query<&Interaction, With<EventLogPanel>>,
+) {
+    let hovered = panels
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    let mut lines = wheel_events
+        .iter()
+        .filter(|_| hovered)
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum::<f32>()
+        .round() as isize;
+    if keys.just_pressed(KeyCode::PageUp) {
+        lines += VISIBLE_ENTRIES as isize;
+    }
+    if keys.just_pressed(KeyCode::PageDown) {
+        lines -= VISIBLE_ENTRIES as isize;
+    }
+    if lines != 0 {
+        event_log.scroll(lines);
+    }
+}
+
+fn update_event_log_panel(
+    event_log: Res<EventLog>,
+    font: Option<Res<AocFont>>,
+    mut texts: Query<&mut Text, With<EventLogText>>,
+) {
+    let font = match font {
+        Some(font) if event_log.is_changed() => font,
+        _ => return,
+    };
+    let style = |warning: bool| TextStyle {
+        font: font.font_handle.clone(),
+        font_size: 18.0,
+        color: if warning { Color::ORANGE } else { NORMAL_TEXT },
+    };
+    let mut sections = vec![TextSection {
+        value: match event_log.scroll {
+            0 => "Events".to_string(),
+            scroll => format!("Events, {} newer below", scroll),
+        },
+        style: TextStyle {
+            color: Color::GOLD,
+            ..style(false)
+        },
+    }];
+    sections.extend(event_log.visible().map(|entry| TextSection {
+        value: format!("\n{}", entry.line()),
+        style: style(entry.warning),
+    }));
+    for mut text in texts.iter_mut() {
+        text.sections = sections.clone();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn log_with(count: usize) -> EventLog {
+        let mut event_log = EventLog::default();
+        for step in 0..count {
+            event_log.push(Some(step as u64), "event");
+        }
+        event_log
+    }
+
+    fn visible_steps(event_log: &EventLog) -> Vec<u64> {
+        event_log.visible().filter_map(|entry| entry.step).collect()
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut event_log = log_with(3);
+        assert_eq!(visible_steps(&event_log), [0, 1, 2]);
+        event_log.scroll(5);
+        assert_eq!(visible_steps(&event_log), [0, 1, 2]);
+
+        let mut event_log = log_with(12);
+        assert_eq!(visible_steps(&event_log), [4, 5, 6, 7, 8, 9, 10, 11]);
+        event_log.scroll(3);
+        assert_eq!(visible_steps(&event_log), [1, 2, 3, 4, 5, 6, 7, 8]);
+        event_log.push(Some(12), "event");
+        assert_eq!(visible_steps(&event_log), [1, 2, 3, 4, 5, 6, 7, 8]);
+        event_log.scroll(-100);
+        assert_eq!(visible_steps(&event_log), [5, 6, 7, 8, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_entry_line() {
+        let mut event_log = EventLog::default();
+        event_log.warn(None, "bad line");
+        event_log.push(Some(7), "found");
+        let lines = event_log.visible().map(LogEntry::line).collect::<Vec<_>>();
+        assert_eq!(lines, ["bad line", "[step 7] found"]);
+        assert!(event_log.visible().next().unwrap().warning);
+    }
+}
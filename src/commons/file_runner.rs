@@ -1,32 +1,190 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use std::time::{Duration, Instant};
+
+use bevy::{asset::AssetPlugin, prelude::*};
+
+use super::{
+    answers::{CurrentAnswer, FinalAnswer},
+    aoc_common::{AocFont, InputLines},
+    event_log::EventLog,
+    fps::StepTime,
+    hud::ElapsedSteps,
+    playback::Playback,
+    puzzle::{Puzzle, PuzzleInfo},
 };
 
-use bevy::prelude::*;
+/// Steps taken in each `app.update()`, since there is no frame time to pace them.
+const STEPS_PER_UPDATE: u32 = 4096;
+/// A run stops without an answer after this many steps, for inputs that never finish such
+/// as a day 1 part 2 input whose frequency only ever grows.
+const MAX_STEPS: u64 = 1_000_000;
 
-struct FileInput(String);
+/// Lines of the input, handed to the puzzle's `InputLines` once its setup spawned them.
+struct RunnerInput(Vec<String>);
 
-fn my_runner(mut app: App, file_name: &str) {
-    println!("Type stuff into the console");
-    let buffered_reader = BufReader::new(File::open(file_name).unwrap());
-    for line in buffered_reader.lines() {
-        {
-            let mut input = app.world.get_resource_mut::<FileInput>().unwrap();
-            input.0 = line.unwrap();
-        }
+/// Progress of a headless run, available as a resource while the app updates.
+#[derive(Default)]
+pub struct RunReport {
+    pub steps: u64,
+    pub finished: bool,
+    /// Answer reported by the puzzle, none if it ran out of steps first.
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    /// Steps after which the run gives up on an answer.
+    pub max_steps: u64,
+    /// Stored answer the run is checked against, if there is one for the input.
+    pub expected: Option<String>,
+}
+
+impl RunReport {
+    pub fn is_correct(&self) -> Option<bool> {
+        self.expected
+            .as_ref()
+            .map(|expected| self.answer.as_ref() == Some(expected))
+    }
+}
+
+/// Builds an app that runs the puzzle's own setup and step systems with [`MinimalPlugins`],
+/// so no window or renderer is created. Each `app.update()` takes [`STEPS_PER_UPDATE`] steps.
+pub fn build_app(puzzle: &dyn Puzzle, lines: Vec<String>, expected: Option<String>) -> App {
+    let info = puzzle.info();
+    let state = info.state();
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        // Puzzles draw into images, which only need the asset storage
+        .add_plugin(AssetPlugin)
+        .add_asset::<Image>()
+        .insert_resource(AocFont {
+            font_handle: Handle::default(),
+        })
+        .init_resource::<Input<KeyCode>>()
+        .init_resource::<FinalAnswer>()
+        .init_resource::<CurrentAnswer>()
+        .init_resource::<EventLog>()
+        .init_resource::<ElapsedSteps>()
+        .init_resource::<StepTime>()
+        .insert_resource(Playback::fixed(STEPS_PER_UPDATE))
+        .insert_resource(RunnerInput(lines))
+        .insert_resource(RunReport {
+            max_steps: MAX_STEPS,
+            expected,
+            ..Default::default()
+        })
+        .insert_resource(info)
+        .add_state(state.clone())
+        .add_system_set(puzzle.setup(SystemSet::on_enter(state.clone())))
+        .add_system_set(puzzle.step(SystemSet::on_update(state)))
+        .add_system_to_stage(CoreStage::PreUpdate, fill_input_lines)
+        .add_system_to_stage(CoreStage::PostUpdate, update_report)
+        .set_runner(file_runner);
+    app
+}
+
+/// Updates the app until the puzzle has reported its answer or ran out of steps.
+pub fn update_until_finished(app: &mut App) {
+    let start = Instant::now();
+    while !app.world.get_resource::<RunReport>().unwrap().finished {
         app.update();
     }
+    app.world.get_resource_mut::<RunReport>().unwrap().elapsed = start.elapsed();
 }
 
-fn print_system(input: Res<FileInput>) {
-    println!("You typed: {}", input.0);
+fn file_runner(mut app: App) {
+    update_until_finished(&mut app);
+    let info = app.world.get_resource::<PuzzleInfo>().unwrap();
+    let report = app.world.get_resource::<RunReport>().unwrap();
+    println!(
+        "{} day {} part {} ({})",
+        info.year, info.day, info.part, info.title
+    );
+    match &report.answer {
+        Some(answer) => println!("answer: {}", answer),
+        None => println!("no answer after {} steps", report.max_steps),
+    }
+    println!("steps: {}", report.steps);
+    println!("elapsed: {:?}", report.elapsed);
+    match (report.is_correct(), &report.expected) {
+        (Some(true), _) => println!("correct"),
+        (Some(false), Some(expected)) => {
+            println!("wrong, expected: {}", expected);
+            std::process::exit(1);
+        }
+        _ => println!("unverified"),
+    }
+    // A run that stalled is a failure even without an answer to check it against
+    if report.answer.is_none() {
+        std::process::exit(1);
+    }
 }
 
-pub fn run() {
-    App::build()
-        .insert_resource(FileInput(String::new()))
-        .set_runner(|app| my_runner(app, "inputs/2018/day1.txt"))
-        .add_system(print_system.system())
-        .run();
+fn fill_input_lines(input: Res<RunnerInput>, mut query: Query<&mut InputLines>) {
+    for mut input_lines in query.iter_mut().filter(|input_lines| !input_lines.loaded) {
+        *input_lines = InputLines::from_lines(input.0.clone());
+    }
+}
+
+fn update_report(
+    final_answer: Res<FinalAnswer>,
+    elapsed: Res<ElapsedSteps>,
+    mut report: ResMut<RunReport>,
+) {
+    report.steps = elapsed.0;
+    if final_answer.is_reported() {
+        report.answer = final_answer.0.clone();
+        report.finished = true;
+    } else if elapsed.0 >= report.max_steps {
+        report.finished = true;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aoc_2018;
+    use crate::commons::{
+        answers::{answers_path, Answers},
+        puzzle_input::InputSource,
+    };
+
+    fn read_asset(path: &str) -> Vec<u8> {
+        std::fs::read(format!("{}/assets/{}", env!("CARGO_MANIFEST_DIR"), path)).unwrap()
+    }
+
+    #[test]
+    fn test_run_against_answers() {
+        let answers = read_asset(&answers_path(2018));
+        let answers = Answers::parse(std::str::from_utf8(&answers).unwrap()).unwrap();
+        for puzzle in aoc_2018::puzzles() {
+            let info = puzzle.info();
+            for (source, path) in [
+                (InputSource::Sample, puzzle.sample_path()),
+                (InputSource::Real, puzzle.input_path()),
+            ] {
+                let expected = answers
+                    .expected(source, info.day, info.part)
+                    .map(|answer| answer.to_string());
+                assert!(expected.is_some(), "no answer for {}", path);
+                let lines = (puzzle.line_splitter())(&read_asset(&path)).unwrap();
+                let mut app = build_app(puzzle.as_ref(), lines, expected);
+                update_until_finished(&mut app);
+                let report = app.world.get_resource::<RunReport>().unwrap();
+                assert!(report.steps > 0);
+                assert_eq!(report.is_correct(), Some(true), "wrong answer for {}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let puzzle = aoc_2018::puzzles()
+            .into_iter()
+            .find(|puzzle| puzzle.info().day == 1 && puzzle.info().part == 2)
+            .unwrap();
+        let mut app = build_app(puzzle.as_ref(), vec!["+1".to_string()], None);
+        app.world.get_resource_mut::<RunReport>().unwrap().max_steps = 20_000;
+        update_until_finished(&mut app);
+        let report = app.world.get_resource::<RunReport>().unwrap();
+        assert_eq!(report.answer, None);
+        assert!(report.steps >= 20_000);
+        assert_eq!(report.is_correct(), None);
+    }
 }
@@ -1,65 +1,361 @@
-use super::constants::*;
+use super::{constants::*, hud::ElapsedSteps};
 use bevy::{
-    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    diagnostic::{
+        Diagnostic, DiagnosticId, Diagnostics, EntityCountDiagnosticsPlugin,
+        FrameTimeDiagnosticsPlugin,
+    },
     prelude::*,
+    utils::Duration,
 };
+
+/// Frame rate overlay, expanded with F3 into a panel of diagnostics with a sparkline each.
 pub struct FpsPlugin;
 
 impl Plugin for FpsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(FrameTimeDiagnosticsPlugin::default())
+            .add_plugin(EntityCountDiagnosticsPlugin)
+            .init_resource::<StepTime>()
+            .add_startup_system(setup_diagnostics)
             .add_startup_system(setup_fps)
+            .add_system(toggle_diagnostics)
+            .add_system_to_stage(CoreStage::PostUpdate, measure_step_time)
+            .add_system(measure_steps_per_second)
             .add_system(fps_update_system);
     }
 }
 
-// A unit struct to help identify the FPS UI component, since there may be many Text components
+/// Input steps the active puzzle took per second.
+pub const STEPS_PER_SECOND: DiagnosticId =
+    DiagnosticId::from_u128(0x3f6e2c1a_8b4d_4e7f_9a1c_5d2b7e8f0a61);
+/// Milliseconds the active puzzle's solver spent stepping each frame.
+pub const STEP_TIME: DiagnosticId = DiagnosticId::from_u128(0x7a9c4e2b_1d3f_4b6a_8e5c_2f1a9d7b3c42);
+/// Size of the main data structure of the active puzzle, measured by the puzzle itself.
+pub const PUZZLE_DATA: DiagnosticId =
+    DiagnosticId::from_u128(0xc2d8f1e4_6a5b_4c3d_9f7e_8b1a2c4d6e83);
+
+const HISTORY_LENGTH: usize = 60;
+/// Bars in each sparkline, the most recent measurements of the diagnostic.
+const SPARKLINE_SAMPLES: usize = 40;
+/// Measurements older than this are left over from a previous puzzle and not shown.
+const STALE_SECONDS: f32 = 1.0;
+
+struct PanelRow {
+    id: DiagnosticId,
+    label: &'static str,
+    /// Shows the average over the history rather than the last measurement.
+    smoothed: bool,
+    /// Shown while there is no recent measurement.
+    missing: &'static str,
+}
+
+const FPS_ROW: PanelRow = PanelRow {
+    id: FrameTimeDiagnosticsPlugin::FPS,
+    label: "FPS",
+    smoothed: true,
+    missing: "-",
+};
+
+const DETAIL_ROWS: [PanelRow; 4] = [
+    PanelRow {
+        id: STEPS_PER_SECOND,
+        label: "Steps/s",
+        smoothed: true,
+        missing: "-",
+    },
+    PanelRow {
+        id: STEP_TIME,
+        label: "Step time",
+        smoothed: true,
+        missing: "-",
+    },
+    PanelRow {
+        id: EntityCountDiagnosticsPlugin::ENTITY_COUNT,
+        label: "Entities",
+        smoothed: false,
+        missing: "-",
+    },
+    PanelRow {
+        id: PUZZLE_DATA,
+        label: "Puzzle data",
+        smoothed: false,
+        // Puzzles without a probe of their own never measure it
+        missing: "n/a",
+    },
+];
+
+fn setup_diagnostics(mut diagnostics: ResMut<Diagnostics>) {
+    diagnostics.add(Diagnostic::new(
+        STEPS_PER_SECOND,
+        "steps_per_second",
+        HISTORY_LENGTH,
+    ));
+    diagnostics.add(Diagnostic::new(STEP_TIME, "step_time", HISTORY_LENGTH).with_suffix("ms"));
+    diagnostics.add(Diagnostic::new(PUZZLE_DATA, "puzzle_data", HISTORY_LENGTH));
+}
+
+/// Value of a diagnostic in the panel, averaged over its history when the flag is set.
 #[derive(Component)]
-struct FpsText;
+struct FpsText(DiagnosticId, bool, &'static str);
+
+#[derive(Component)]
+struct SparklineBar {
+    id: DiagnosticId,
+    index: usize,
+}
+
+/// Part of the panel, detail parts are hidden until F3 is pressed.
+#[derive(Component)]
+struct PanelPart {
+    detail: bool,
+}
 
 fn setup_fps(mut commands: Commands, asset_server: Res<AssetServer>) {
     let handle = asset_server.load(FONT_PATH);
     commands
-        .spawn_bundle(Text2dBundle {
-            // Use `Text` directly
-            text: Text {
-                // Construct a `Vec` of `TextSection`s
-                sections: vec![
-                    TextSection {
-                        value: "FPS: ".to_string(),
-                        style: TextStyle {
-                            font: handle.clone(),
-                            font_size: 40.0,
-                            color: Color::WHITE,
-                        },
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: TextStyle {
-                            font: handle,
-                            font_size: 40.0,
-                            color: Color::GOLD,
-                        },
-                    },
-                ],
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(10.0),
+                    top: Val::Px(10.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: Rect::all(Val::Px(6.0)),
                 ..Default::default()
             },
-            transform: Transform::from_xyz(
-                (WINDOW_WIDTH / 2.0) - 175.0,
-                (WINDOW_HEIGHT / 2.0) - 30.0,
-                0.0,
-            ),
+            color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
             ..Default::default()
         })
-        .insert(FpsText);
+        .with_children(|parent| {
+            spawn_row(parent, &handle, &FPS_ROW, false);
+            for row in DETAIL_ROWS.iter() {
+                spawn_row(parent, &handle, row, true);
+            }
+        });
 }
 
-fn fps_update_system(diagnostics: Res<Diagnostics>, mut query: Query<(&mut Text, With<FpsText>)>) {
-    for mut text in query.iter_mut() {
-        if let Some(fps) = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS) {
-            if let Some(average) = fps.average() {
-                text.0.sections[1].value = format!("{:.2}", average);
-            }
+fn spawn_row(parent: &mut ChildBuilder, font: &Handle<Font>, row: &PanelRow, detail: bool) {
+    let visibility = Visibility {
+        is_visible: !detail,
+    };
+    let display = if detail { Display::None } else { Display::Flex };
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                display,
+                ..Default::default()
+            },
+            color: Color::NONE.into(),
+            visibility: visibility.clone(),
+            ..Default::default()
+        })
+        .insert(PanelPart { detail })
+        .with_children(|row_parent| {
+            row_parent
+                .spawn_bundle(TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection {
+                                value: format!("{}: ", row.label),
+                                style: TextStyle {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    color: Color::WHITE,
+                                },
+                            },
+                            TextSection {
+                                value: "-".to_string(),
+                                style: TextStyle {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                    style: Style {
+                        size: Size::new(Val::Px(190.0), Val::Auto),
+                        display,
+                        ..Default::default()
+                    },
+                    visibility: visibility.clone(),
+                    ..Default::default()
+                })
+                .insert(PanelPart { detail })
+                .insert(FpsText(row.id, row.smoothed, row.missing));
+            row_parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(SPARKLINE_SAMPLES as f32 * 3.0), Val::Px(18.0)),
+                        align_items: AlignItems::FlexStart,
+                        display,
+                        ..Default::default()
+                    },
+                    color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    visibility: visibility.clone(),
+                    ..Default::default()
+                })
+                .insert(PanelPart { detail })
+                .with_children(|bars| {
+                    for index in 0..SPARKLINE_SAMPLES {
+                        bars.spawn_bundle(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(3.0), Val::Percent(0.0)),
+                                display,
+                                ..Default::default()
+                            },
+                            color: Color::GOLD.into(),
+                            visibility: visibility.clone(),
+                            ..Default::default()
+                        })
+                        .insert(PanelPart { detail })
+                        .insert(SparklineBar { id: row.id, index });
+                    }
+                });
+        });
+}
+
+fn toggle_diagnostics(
+    keys: Res<Input<KeyCode>>,
+    mut parts: Query<(&PanelPart, &mut Visibility, &mut Style)>,
+) {
+    if !keys.just_pressed(KeyCode::F3) {
+        return;
+    }
+    for (part, mut visibility, mut style) in parts.iter_mut() {
+        if part.detail {
+            visibility.is_visible = !visibility.is_visible;
+            style.display = if visibility.is_visible {
+                Display::Flex
+            } else {
+                Display::None
+            };
         }
     }
 }
+
+/// Time the active puzzle's solver spent stepping in the current frame, added up by
+/// [`PuzzleSteps`](super::puzzle::PuzzleSteps) so the rest of the frame is left out.
+#[derive(Default)]
+pub struct StepTime(pub Duration);
+
+fn measure_step_time(
+    mut step_time: ResMut<StepTime>,
+    mut diagnostics: ResMut<Diagnostics>,
+    elapsed: Option<Res<ElapsedSteps>>,
+) {
+    let time = std::mem::take(&mut step_time.0);
+    if elapsed.is_some() {
+        diagnostics.add_measurement(STEP_TIME, time.as_secs_f64() * 1000.0);
+    }
+}
+
+fn measure_steps_per_second(
+    time: Res<Time>,
+    mut diagnostics: ResMut<Diagnostics>,
+    elapsed: Option<Res<ElapsedSteps>>,
+    mut previous: Local<u64>,
+) {
+    let elapsed = match elapsed {
+        Some(elapsed) => elapsed.0,
+        None => return,
+    };
+    // A restarted puzzle counts from zero again
+    let steps = elapsed.saturating_sub(*previous);
+    *previous = elapsed;
+    if time.delta_seconds_f64() > 0.0 {
+        diagnostics.add_measurement(STEPS_PER_SECOND, steps as f64 / time.delta_seconds_f64());
+    }
+}
+
+/// Heights between 0 and 1 of the last `samples` values given newest first, oldest on the
+/// left and padded with empty bars while the history is short.
+fn sparkline(newest_first: &[f64], samples: usize) -> Vec<f32> {
+    let values = &newest_first[..newest_first.len().min(samples)];
+    let max = values.iter().copied().fold(0.0, f64::max);
+    let mut heights = vec![0.0; samples - values.len()];
+    heights.extend(values.iter().rev().map(
+        |value| {
+            if max > 0.0 {
+                (value / max) as f32
+            } else {
+                0.0
+            }
+        },
+    ));
+    heights
+}
+
+/// Value shown in a panel row. `Diagnostic::value` is the oldest measurement in the history,
+/// so rows that are not smoothed read the newest one themselves.
+fn shown_value(diagnostic: &Diagnostic, smoothed: bool) -> Option<f64> {
+    if smoothed {
+        diagnostic.average()
+    } else {
+        diagnostic
+            .measurements()
+            .next()
+            .map(|measurement| measurement.value)
+    }
+}
+
+fn fps_update_system(
+    diagnostics: Res<Diagnostics>,
+    mut texts: Query<(&mut Text, &FpsText)>,
+    mut bars: Query<(&mut Style, &SparklineBar)>,
+) {
+    let fresh = |id: DiagnosticId| {
+        diagnostics.get(id).filter(|diagnostic| {
+            diagnostic
+                .measurements()
+                .next()
+                .map_or(false, |measurement| {
+                    measurement.time.elapsed().as_secs_f32() < STALE_SECONDS
+                })
+        })
+    };
+    for (mut text, FpsText(id, smoothed, missing)) in texts.iter_mut() {
+        let value = fresh(*id).and_then(|diagnostic| {
+            shown_value(diagnostic, *smoothed)
+                .map(|value| format!("{:.2}{}", value, diagnostic.suffix))
+        });
+        text.sections[1].value = value.unwrap_or_else(|| missing.to_string());
+    }
+    let mut heights = std::collections::HashMap::new();
+    for (mut style, bar) in bars.iter_mut() {
+        let heights = heights.entry(bar.id).or_insert_with(|| {
+            let values = fresh(bar.id)
+                .map(|diagnostic| diagnostic.values().copied().collect::<Vec<_>>())
+                .unwrap_or_default();
+            sparkline(&values, SPARKLINE_SAMPLES)
+        });
+        style.size.height = Val::Percent(heights[bar.index] * 100.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[4.0, 2.0], 4), [0.0, 0.0, 0.5, 1.0]);
+        assert_eq!(sparkline(&[1.0, 2.0, 4.0], 2), [1.0, 0.5]);
+        assert_eq!(sparkline(&[], 2), [0.0, 0.0]);
+        assert_eq!(sparkline(&[0.0], 1), [0.0]);
+    }
+
+    #[test]
+    fn test_shown_value() {
+        let mut diagnostic = Diagnostic::new(PUZZLE_DATA, "puzzle_data", HISTORY_LENGTH);
+        assert_eq!(shown_value(&diagnostic, false), None);
+        diagnostic.add_measurement(1000.0);
+        diagnostic.add_measurement(4.0);
+        assert_eq!(shown_value(&diagnostic, false), Some(4.0));
+        assert_eq!(shown_value(&diagnostic, true), Some(502.0));
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use super::{
+    answers::{answers_path, Answers},
+    aoc_common::AOCState,
+    file_runner,
+    parse::validate_lines,
+    puzzle::Puzzle,
+    puzzle_input::InputSource,
+};
+
+/// Solves a puzzle without opening a window. The [`file_runner`] prints the answer, the
+/// number of steps taken and the time spent solving, and checks the answer against the
+/// stored one when the puzzle's own input is used.
+pub fn solve(
+    puzzles: &[Box<dyn Puzzle>],
+    problem: &AOCState,
+    input: Option<&str>,
+) -> Result<(), String> {
+    let puzzle = puzzles
+        .iter()
+        .find(|puzzle| puzzle.info().state() == *problem)
+        .ok_or_else(|| format!("no puzzle registered for {:?}", problem))?;
+    let path = match input {
+        Some(input) => PathBuf::from(input),
+        None => asset_root().join(puzzle.input_path()),
+    };
+    let bytes = std::fs::read(&path)
+        .map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+    let lines = (puzzle.line_splitter())(&bytes)
+        .and_then(|lines| {
+            validate_lines(&lines, puzzle.line_validator())?;
+            Ok(lines)
+        })
+        .map_err(|error| format!("invalid input in {}, {}", path.display(), error))?;
+    let source = match input {
+        Some(_) => InputSource::Custom,
+        None => InputSource::Real,
+    };
+    let expected = expected_answer(problem, source);
+    file_runner::build_app(puzzle.as_ref(), lines, expected).run();
+    Ok(())
+}
+
+/// A missing or broken answers file leaves the run unverified.
+fn expected_answer(problem: &AOCState, source: InputSource) -> Option<String> {
+    let text = std::fs::read_to_string(asset_root().join(answers_path(problem.year))).ok()?;
+    let answers = Answers::parse(&text).ok()?;
+    answers
+        .expected(source, problem.day, problem.part)
+        .map(|answer| answer.to_string())
+}
+
+/// Same lookup the asset server uses for the assets folder on native builds.
+fn asset_root() -> PathBuf {
+    let root = match std::env::var("CARGO_MANIFEST_DIR") {
+        Ok(manifest_dir) => PathBuf::from(manifest_dir),
+        Err(_) => std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(PathBuf::from))
+            .unwrap_or_default(),
+    };
+    root.join("assets")
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use super::{
+    answers::{CurrentAnswer, FinalAnswer},
+    aoc_common::{AOCState, AocFont, InputLines, PuzzleEntity},
+    constants::NORMAL_TEXT,
+    puzzle::PuzzleRegistry,
+    puzzle_input::InputStatus,
+};
+
+/// Steps the puzzle's solver took since the puzzle was launched or restarted, counted by
+/// [`PuzzleSteps`](super::puzzle::PuzzleSteps).
+#[derive(Default)]
+pub struct ElapsedSteps(pub u64);
+
+#[derive(Component)]
+pub struct HudText;
+
+#[derive(Component)]
+pub struct ProgressFill;
+
+#[derive(Component)]
+pub struct ProgressLabel;
+
+const PROGRESS_WIDTH: f32 = 300.0;
+
+/// Fraction of the current pass over the input read so far, and the pass number counted
+/// from 1. Repeating inputs start a new pass each time they wrap around.
+pub fn input_progress(input: &InputLines) -> (f32, usize) {
+    let len = input.input_lines.len();
+    if len == 0 {
+        return (0.0, 1);
+    }
+    let read = input.read_pos.saturating_sub(input.wraps() * len).min(len);
+    (read as f32 / len as f32, input.wraps() + 1)
+}
+
+/// Spawns the overlay in the top left naming the puzzle and its input, with a progress bar,
+/// the elapsed steps and the answer so far.
+pub fn setup_hud(
+    mut commands: Commands,
+    aoc_font: Res<AocFont>,
+    state: Res<State<AOCState>>,
+    registry: Res<PuzzleRegistry>,
+) {
+    commands.insert_resource(ElapsedSteps::default());
+    let puzzle = state.current();
+    let title = match registry.get(puzzle) {
+        Some(info) => format!(
+            "{} day {} part {}: {}",
+            info.year, info.day, info.part, info.title
+        ),
+        None => format!("{} day {} part {}", puzzle.year, puzzle.day, puzzle.part),
+    };
+    let style = TextStyle {
+        font: aoc_font.font_handle.clone(),
+        font_size: 20.0,
+        color: NORMAL_TEXT,
+    };
+    let text = |value: String, style: &TextStyle| TextBundle {
+        text: Text::with_section(value, style.clone(), Default::default()),
+        style: Style {
+            margin: Rect::all(Val::Px(2.0)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(10.0),
+                    top: Val::Px(10.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: Rect::all(Val::Px(8.0)),
+                ..Default::default()
+            },
+            color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(text(
+                title,
+                &TextStyle {
+                    color: Color::GOLD,
+                    ..style.clone()
+                },
+            ));
+            parent
+                .spawn_bundle(text(String::new(), &style))
+                .insert(InputStatus);
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(PROGRESS_WIDTH), Val::Px(12.0)),
+                        margin: Rect::all(Val::Px(2.0)),
+                        ..Default::default()
+                    },
+                    color: Color::rgb(0.25, 0.25, 0.25).into(),
+                    ..Default::default()
+                })
+                .with_children(|bar| {
+                    bar.spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                            ..Default::default()
+                        },
+                        color: Color::rgb(0.35, 0.75, 0.35).into(),
+                        ..Default::default()
+                    })
+                    .insert(ProgressFill);
+                });
+            parent
+                .spawn_bundle(text(String::new(), &style))
+                .insert(ProgressLabel);
+            parent
+                .spawn_bundle(text(String::new(), &style))
+                .insert(HudText);
+        })
+        .insert(PuzzleEntity);
+}
+
+pub fn unload_hud(mut commands: Commands) {
+    commands.remove_resource::<ElapsedSteps>();
+}
+
+pub fn update_progress(
+    inputs: Query<&InputLines>,
+    mut fills: Query<&mut Style, With<ProgressFill>>,
+    mut labels: Query<&mut Text, With<ProgressLabel>>,
+) {
+    let input = match inputs.iter().find(|input| input.loaded) {
+        Some(input) => input,
+        None => return,
+    };
+    let (progress, pass) = input_progress(input);
+    for mut style in fills.iter_mut() {
+        style.size.width = Val::Percent(progress * 100.0);
+    }
+    let read = (progress * input.input_lines.len() as f32).round();
+    let mut label = format!("{} / {} lines", read, input.input_lines.len());
+    if pass > 1 {
+        label.push_str(&format!(", pass {}", pass));
+    }
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = label.clone();
+    }
+}
+
+pub fn update_hud(
+    final_answer: Res<FinalAnswer>,
+    current_answer: Res<CurrentAnswer>,
+    elapsed: Res<ElapsedSteps>,
+    inputs: Query<&InputLines>,
+    mut texts: Query<&mut Text, With<HudText>>,
+) {
+    if !inputs.iter().any(|input| input.loaded) {
+        return;
+    }
+    let current = current_answer.0.as_ref();
+    let answer = final_answer
+        .0
+        .as_ref()
+        .or(current)
+        .map_or("-", |answer| answer.as_str());
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format!("Steps: {}  Answer: {}", elapsed.0, answer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_progress() {
+        let mut input = InputLines::from_lines(vec!["+1".to_string(), "-2".to_string()]);
+        assert_eq!(input_progress(&input), (0.0, 1));
+        input.next(true);
+        assert_eq!(input_progress(&input), (0.5, 1));
+        input.next(true);
+        assert_eq!(input_progress(&input), (1.0, 1));
+        input.next(true);
+        assert_eq!(input_progress(&input), (0.5, 2));
+        assert_eq!(input_progress(&InputLines::default()), (0.0, 1));
+    }
+}
@@ -1,5 +1,21 @@
+pub mod answers;
 pub mod aoc_common;
+pub mod axis;
+pub mod bar_chart;
+pub mod cli;
 pub mod constants;
+pub mod event_log;
+// Solving without a window reads files and prints, neither of which a page can do
+#[cfg(not(target_arch = "wasm32"))]
+pub mod file_runner;
 pub mod fps;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
+pub mod hud;
+pub mod parse;
+pub mod playback;
+pub mod puzzle;
+pub mod puzzle_input;
+pub mod scale;
 //pub mod state_test;
 pub mod window_setup;
@@ -0,0 +1,130 @@
+use std::fmt;
+
+/// A line of puzzle input that could not be read or parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// Line number counted from 1, as shown by text editors.
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} ({:?})", self.line, self.message, self.text)
+    }
+}
+
+/// Checks a single line of a puzzle's input, returning why it cannot be parsed.
+pub type LineValidator = fn(&str) -> Result<(), String>;
+
+pub fn accept_any_line(_line: &str) -> Result<(), String> {
+    Ok(())
+}
+
+/// Splits raw input into the lines handed to a puzzle.
+pub type LineSplitter = fn(&[u8]) -> Result<Vec<String>, ParseError>;
+
+/// Splits raw input into lines, reporting the first line that is not valid UTF-8. A leading
+/// byte order mark, trailing whitespace including `\r` of CRLF line endings, and blank
+/// lines at the end are dropped, so inputs saved by any editor read the same.
+pub fn split_lines(bytes: &[u8]) -> Result<Vec<String>, ParseError> {
+    split_with(bytes, str::trim_end)
+}
+
+/// Same as [`split_lines`], but only the `\r` of CRLF line endings is dropped from each
+/// line, for grids whose cells can be spaces.
+// Picked through `Puzzle::line_splitter`, which no day so far needs
+#[allow(dead_code)]
+pub fn split_grid_lines(bytes: &[u8]) -> Result<Vec<String>, ParseError> {
+    split_with(bytes, |line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+fn split_with(bytes: &[u8], trim: fn(&str) -> &str) -> Result<Vec<String>, ParseError> {
+    let text = std::str::from_utf8(bytes).map_err(|error| {
+        let valid = &bytes[..error.valid_up_to()];
+        let start = valid
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+        let end = bytes[start..]
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(bytes.len(), |newline| start + newline);
+        ParseError {
+            line: valid.iter().filter(|byte| **byte == b'\n').count() + 1,
+            text: String::from_utf8_lossy(&bytes[start..end]).into_owned(),
+            message: "not valid UTF-8".to_string(),
+        }
+    })?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut lines = text
+        .lines()
+        .map(|line| trim(line).to_string())
+        .collect::<Vec<_>>();
+    while lines.last().map_or(false, |line| line.is_empty()) {
+        lines.pop();
+    }
+    Ok(lines)
+}
+
+/// Runs the validator over every line, stopping at the first one it rejects.
+pub fn validate_lines(lines: &[String], validator: LineValidator) -> Result<(), ParseError> {
+    for (index, line) in lines.iter().enumerate() {
+        validator(line).map_err(|message| ParseError {
+            line: index + 1,
+            text: line.clone(),
+            message,
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn validate_number(line: &str) -> Result<(), String> {
+        line.parse::<i32>()
+            .map(|_| ())
+            .map_err(|_| "expected a number".to_string())
+    }
+
+    #[test]
+    fn test_split_lines() {
+        assert_eq!(split_lines(b"+1\n-2\n").unwrap(), ["+1", "-2"]);
+        assert_eq!(
+            split_lines(b"\xef\xbb\xbf+1 \r\n\r\n-2\t\r\n\n\n").unwrap(),
+            ["+1", "", "-2"]
+        );
+        assert_eq!(split_lines(b"#. \r\n .#\r\n \n").unwrap(), ["#.", " .#"]);
+        assert_eq!(
+            split_lines(b"+1\n-\xff2\n+3").unwrap_err(),
+            ParseError {
+                line: 2,
+                text: "-\u{fffd}2".to_string(),
+                message: "not valid UTF-8".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_grid_lines() {
+        assert_eq!(
+            split_grid_lines(b"\xef\xbb\xbf#. \r\n .#\r\n\n").unwrap(),
+            ["#. ", " .#"]
+        );
+        assert_eq!(split_grid_lines(b"  \n").unwrap(), ["  "]);
+        assert!(split_grid_lines(b"\xff").is_err());
+    }
+
+    #[test]
+    fn test_validate_lines() {
+        let lines = vec!["1".to_string(), "2".to_string(), "three".to_string()];
+        assert!(validate_lines(&lines[..2], validate_number).is_ok());
+        let error = validate_lines(&lines, validate_number).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.to_string(), "line 3: expected a number (\"three\")");
+        assert!(validate_lines(&lines, accept_any_line).is_ok());
+    }
+}
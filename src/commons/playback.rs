@@ -0,0 +1,302 @@
+use bevy::prelude::*;
+
+use super::{
+    aoc_common::{AOCState, AocFont, PuzzleEntity},
+    constants::{HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON},
+};
+
+const MIN_STEPS_PER_SECOND: f32 = 1.0;
+const MAX_STEPS_PER_SECOND: f32 = 65536.0;
+
+pub struct PlaybackPlugin;
+
+impl Plugin for PlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Playback::default())
+            .add_system_to_stage(CoreStage::PreUpdate, update_playback)
+            .add_system_set(SystemSet::on_enter(AOCState::restart()).with_system(finish_restart));
+    }
+}
+
+/// Decides how many input steps the active puzzle may take in the current frame.
+/// Step systems take these steps through [`PuzzleSteps`](super::puzzle::PuzzleSteps).
+pub struct Playback {
+    pub playing: bool,
+    pub steps_per_second: f32,
+    step_requested: bool,
+    accumulated: f32,
+    steps: u32,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            playing: true,
+            steps_per_second: 60.0,
+            step_requested: false,
+            accumulated: 0.0,
+            steps: 0,
+        }
+    }
+}
+
+impl Playback {
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Playback taking the same number of steps every frame, for the headless runner.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn fixed(steps: u32) -> Self {
+        Self {
+            steps,
+            ..Default::default()
+        }
+    }
+
+    pub fn reset(&mut self, steps_per_second: f32) {
+        *self = Self {
+            steps_per_second: steps_per_second.clamp(MIN_STEPS_PER_SECOND, MAX_STEPS_PER_SECOND),
+            ..Default::default()
+        };
+    }
+
+    pub fn toggle(&mut self) {
+        self.playing = !self.playing;
+    }
+
+    /// Pauses playback and lets exactly one step through on the next frame.
+    pub fn request_step(&mut self) {
+        self.playing = false;
+        self.step_requested = true;
+    }
+
+    pub fn faster(&mut self) {
+        self.steps_per_second = (self.steps_per_second * 2.0).min(MAX_STEPS_PER_SECOND);
+    }
+
+    pub fn slower(&mut self) {
+        self.steps_per_second = (self.steps_per_second / 2.0).max(MIN_STEPS_PER_SECOND);
+    }
+
+    fn advance(&mut self, delta_seconds: f32) {
+        self.steps = 0;
+        if self.playing {
+            // Never carry more than a second of backlog after a long frame
+            self.accumulated = (self.accumulated + delta_seconds * self.steps_per_second)
+                .min(self.steps_per_second);
+            self.steps = self.accumulated.floor() as u32;
+            self.accumulated -= self.steps as f32;
+        }
+        if self.step_requested {
+            self.steps += 1;
+            self.step_requested = false;
+        }
+    }
+}
+
+fn update_playback(time: Res<Time>, mut playback: ResMut<Playback>) {
+    playback.advance(time.delta_seconds());
+}
+
+#[derive(Component, Clone, Copy)]
+pub enum PlaybackButton {
+    PlayPause,
+    Step,
+    Slower,
+    Faster,
+    Restart,
+}
+
+#[derive(Component)]
+pub struct PlaybackStatus;
+
+fn apply_action(action: PlaybackButton, playback: &mut Playback, state: &mut State<AOCState>) {
+    match action {
+        PlaybackButton::PlayPause => playback.toggle(),
+        PlaybackButton::Step => playback.request_step(),
+        PlaybackButton::Slower => playback.slower(),
+        PlaybackButton::Faster => playback.faster(),
+        PlaybackButton::Restart => {
+            // Pausing the puzzle state runs its cleanup and resuming it runs its setup again
+            let _ = state.push(AOCState::restart());
+        }
+    }
+}
+
+fn finish_restart(mut state: ResMut<State<AOCState>>) {
+    state.pop().unwrap();
+}
+
+pub fn setup_controls(mut commands: Commands, aoc_font: Res<AocFont>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(10.0),
+                    bottom: Val::Px(10.0),
+                    ..Default::default()
+                },
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (label, button) in [
+                ("Play/Pause", PlaybackButton::PlayPause),
+                ("Step", PlaybackButton::Step),
+                ("Slower", PlaybackButton::Slower),
+                ("Faster", PlaybackButton::Faster),
+                ("Restart", PlaybackButton::Restart),
+            ] {
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(110.0), Val::Px(35.0)),
+                            margin: Rect::all(Val::Px(5.0)),
+                            // horizontally center child text
+                            justify_content: JustifyContent::Center,
+                            // vertically center child text
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        color: NORMAL_BUTTON.into(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                label,
+                                TextStyle {
+                                    font: aoc_font.font_handle.clone(),
+                                    font_size: 20.0,
+                                    color: Color::rgb(0.9, 0.9, 0.9),
+                                },
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        });
+                    })
+                    .insert(button);
+            }
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: aoc_font.font_handle.clone(),
+                            font_size: 20.0,
+                            color: Color::GOLD,
+                        },
+                        Default::default(),
+                    ),
+                    style: Style {
+                        margin: Rect::all(Val::Px(5.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(PlaybackStatus);
+        })
+        .insert(PuzzleEntity);
+}
+
+/// Space plays/pauses, Right steps once, Up/Down change the speed and R restarts.
+pub fn playback_keys(
+    keys: Res<Input<KeyCode>>,
+    mut playback: ResMut<Playback>,
+    mut state: ResMut<State<AOCState>>,
+) {
+    // Shift and the arrow keys pan the camera instead
+    if keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift) {
+        return;
+    }
+    let bindings = [
+        (KeyCode::Space, PlaybackButton::PlayPause),
+        (KeyCode::Right, PlaybackButton::Step),
+        (KeyCode::Down, PlaybackButton::Slower),
+        (KeyCode::Up, PlaybackButton::Faster),
+        (KeyCode::R, PlaybackButton::Restart),
+    ];
+    for (key, action) in bindings {
+        if keys.just_pressed(key) {
+            apply_action(action, &mut playback, &mut state);
+        }
+    }
+}
+
+type PlaybackButtonQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Interaction,
+        &'static mut UiColor,
+        &'static PlaybackButton,
+    ),
+    (Changed<Interaction>, With<Button>),
+>;
+
+pub fn playback_buttons(
+    mut playback: ResMut<Playback>,
+    mut state: ResMut<State<AOCState>>,
+    mut query: PlaybackButtonQuery,
+) {
+    for (interaction, mut color, button) in query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *color = PRESSED_BUTTON.into();
+                apply_action(*button, &mut playback, &mut state);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+pub fn update_playback_status(
+    playback: Res<Playback>,
+    mut query: Query<&mut Text, With<PlaybackStatus>>,
+) {
+    let status = if playback.playing {
+        "Playing"
+    } else {
+        "Paused"
+    };
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("{} at {} steps/s", status, playback.steps_per_second);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_advance() {
+        let mut playback = Playback::default();
+        playback.reset(10.0);
+        playback.advance(0.25);
+        assert_eq!(playback.steps(), 2);
+        playback.advance(0.25);
+        assert_eq!(playback.steps(), 3);
+        playback.advance(10.0);
+        assert_eq!(playback.steps(), 10);
+    }
+
+    #[test]
+    fn test_single_step() {
+        let mut playback = Playback::default();
+        playback.request_step();
+        playback.advance(1.0);
+        assert_eq!(playback.steps(), 1);
+        playback.advance(1.0);
+        assert_eq!(playback.steps(), 0);
+    }
+}
@@ -0,0 +1,398 @@
+use std::{marker::PhantomData, ops::DerefMut};
+
+use bevy::{ecs::system::SystemParam, prelude::*, utils::Instant};
+
+use super::{
+    answers::{
+        load_answers, lookup_expected_answer, reset_final_answer, show_answer_badge,
+        unload_answers, FinalAnswer,
+    },
+    aoc_common::{AOCState, AocFont, InputLines, PuzzleEntity},
+    axis::draw_axes,
+    bar_chart::tween_bars,
+    cli::CliArgs,
+    constants::NORMAL_TEXT,
+    event_log::{clear_event_log, setup_event_log},
+    fps::StepTime,
+    hud::{setup_hud, unload_hud, update_hud, update_progress, ElapsedSteps},
+    parse::{accept_any_line, split_lines, LineSplitter, LineValidator},
+    playback::{playback_buttons, playback_keys, setup_controls, update_playback_status, Playback},
+    puzzle_input::{load_puzzle_input, unload_puzzle_input, update_input_status},
+    window_setup::{camera_drag_pan, camera_follow, camera_keys, camera_wheel_zoom, reset_camera},
+};
+
+/// Identity of a puzzle, shared with the menu through the [`PuzzleRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleInfo {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub title: &'static str,
+}
+
+impl PuzzleInfo {
+    pub fn state(&self) -> AOCState {
+        AOCState {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+        }
+    }
+}
+
+/// A single year/day/part visual. Each method receives the system set already bound to the
+/// puzzle's [`AOCState`] and adds the puzzle's systems to it. Entities spawned by a puzzle
+/// should carry [`PuzzleEntity`] so they are despawned when returning to the menu.
+pub trait Puzzle {
+    fn info(&self) -> PuzzleInfo;
+
+    /// Systems run once when the puzzle state is entered.
+    fn setup(&self, set: SystemSet) -> SystemSet;
+
+    /// Systems run every frame while the puzzle is active. Input should only be consumed
+    /// through [`PuzzleSteps`], so the visual takes the same steps as the headless runner.
+    fn step(&self, set: SystemSet) -> SystemSet;
+
+    /// Systems run every frame while the puzzle is active that report the puzzle's own
+    /// diagnostics, such as [`PUZZLE_DATA`](super::fps::PUZZLE_DATA). They are kept apart
+    /// from the step systems and are not run by the headless runner.
+    fn measure(&self, set: SystemSet) -> SystemSet {
+        set
+    }
+
+    /// Systems run once when the puzzle state is left, removing the puzzle's resources.
+    fn cleanup(&self, set: SystemSet) -> SystemSet {
+        set
+    }
+
+    /// Asset path of the real puzzle input.
+    fn input_path(&self) -> String {
+        let info = self.info();
+        format!("inputs/{}/day{}.txt", info.year, info.day)
+    }
+
+    /// Asset path of the example input from the puzzle description.
+    fn sample_path(&self) -> String {
+        let info = self.info();
+        format!("samples/{}/day{}.txt", info.year, info.day)
+    }
+
+    /// Splits the input into lines. Grid puzzles whose cells can be spaces use
+    /// [`split_grid_lines`](super::parse::split_grid_lines) to keep trailing whitespace.
+    fn line_splitter(&self) -> LineSplitter {
+        split_lines
+    }
+
+    /// Checks each line of the input when the puzzle is launched, so a malformed line is
+    /// reported instead of reaching the step systems.
+    fn line_validator(&self) -> LineValidator {
+        accept_any_line
+    }
+
+    /// Speed the playback starts at when the puzzle is launched.
+    fn default_speed(&self) -> f32 {
+        60.0
+    }
+}
+
+/// Solves a puzzle one step at a time without any rendering. The step systems keep the
+/// solver as a component or resource and draw from its state.
+pub trait Solver: Send + Sync + 'static {
+    /// Consumes the next step of the input, returning false once the puzzle is finished.
+    fn step(&mut self, input: &mut InputLines) -> bool;
+
+    fn answer(&self) -> String;
+}
+
+/// Steps the puzzle's [`Solver`] as many times as [`Playback`] allows this frame, counting
+/// the steps taken, timing them and reporting its answer once it has finished.
+#[derive(SystemParam)]
+pub struct PuzzleSteps<'w, 's> {
+    playback: Res<'w, Playback>,
+    final_answer: ResMut<'w, FinalAnswer>,
+    elapsed: ResMut<'w, ElapsedSteps>,
+    step_time: ResMut<'w, StepTime>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> PuzzleSteps<'w, 's> {
+    /// Calls `on_step` after each step the solver took with the steps taken so far, the
+    /// count the HUD shows, returning whether it took any. The solver is only borrowed
+    /// mutably when it steps, so change detection still works.
+    pub fn run<S: Solver, D: DerefMut<Target = S>>(
+        &mut self,
+        solver: &mut D,
+        input: &mut InputLines,
+        mut on_step: impl FnMut(&S, &InputLines, u64),
+    ) -> bool {
+        if !input.loaded || self.final_answer.is_reported() {
+            return false;
+        }
+        let start = Instant::now();
+        let mut stepped = false;
+        for _ in 0..self.playback.steps() {
+            if !self.take_step(solver, input) {
+                break;
+            }
+            stepped = true;
+            on_step(solver, input, self.elapsed.0);
+        }
+        self.step_time.0 += start.elapsed();
+        stepped
+    }
+
+    /// Takes a single step whatever the playback, for steps the visual does not show.
+    pub fn step<S: Solver, D: DerefMut<Target = S>>(
+        &mut self,
+        solver: &mut D,
+        input: &mut InputLines,
+    ) -> bool {
+        let start = Instant::now();
+        let stepped = self.take_step(solver, input);
+        self.step_time.0 += start.elapsed();
+        stepped
+    }
+
+    /// Steps taken since the puzzle was launched or restarted.
+    pub fn elapsed(&self) -> u64 {
+        self.elapsed.0
+    }
+
+    fn take_step<S: Solver, D: DerefMut<Target = S>>(
+        &mut self,
+        solver: &mut D,
+        input: &mut InputLines,
+    ) -> bool {
+        if !solver.step(input) {
+            self.final_answer.report(solver.answer());
+            return false;
+        }
+        self.elapsed.0 += 1;
+        true
+    }
+}
+
+/// Every puzzle registered with the app, in registration order.
+#[derive(Default)]
+pub struct PuzzleRegistry {
+    pub puzzles: Vec<PuzzleInfo>,
+}
+
+impl PuzzleRegistry {
+    pub fn get(&self, state: &AOCState) -> Option<&PuzzleInfo> {
+        self.puzzles.iter().find(|info| info.state() == *state)
+    }
+
+    pub fn contains(&self, state: &AOCState) -> bool {
+        self.get(state).is_some()
+    }
+
+    /// Whether any puzzle matches the given year, day and part, where `None` matches any.
+    pub fn any(&self, year: Option<u16>, day: Option<u8>, part: Option<u8>) -> bool {
+        self.puzzles.iter().any(|info| {
+            year.map_or(true, |year| info.year == year)
+                && day.map_or(true, |day| info.day == day)
+                && part.map_or(true, |part| info.part == part)
+        })
+    }
+
+    /// Title of a day, shared by both of its parts.
+    pub fn title(&self, year: u16, day: u8) -> Option<&'static str> {
+        self.puzzles
+            .iter()
+            .find(|info| info.year == year && info.day == day)
+            .map(|info| info.title)
+    }
+}
+
+pub fn register_puzzles(mut app: App, puzzles: Vec<Box<dyn Puzzle>>) -> App {
+    for puzzle in puzzles {
+        let info = puzzle.info();
+        let state = info.state();
+        let default_speed = puzzle.default_speed();
+        app.add_system_set(
+            puzzle.setup(
+                SystemSet::on_enter(state.clone())
+                    .with_system(setup_controls)
+                    .with_system(setup_hud)
+                    .with_system(clear_event_log)
+                    .with_system(setup_event_log)
+                    .with_system(reset_final_answer)
+                    .with_system(load_answers)
+                    .with_system(load_puzzle_input(
+                        puzzle.input_path(),
+                        puzzle.sample_path(),
+                        puzzle.line_splitter(),
+                        puzzle.line_validator(),
+                    ))
+                    .with_system(
+                        move |mut playback: ResMut<Playback>, cli_args: Option<Res<CliArgs>>| {
+                            let speed = cli_args.and_then(|cli_args| cli_args.speed);
+                            playback.reset(speed.unwrap_or(default_speed))
+                        },
+                    ),
+            ),
+        );
+        app.add_system_set(
+            SystemSet::on_update(state.clone())
+                .with_system(return_to_menu)
+                .with_system(playback_keys)
+                .with_system(playback_buttons)
+                .with_system(update_playback_status)
+                .with_system(update_input_status)
+                .with_system(update_hud)
+                .with_system(update_progress)
+                .with_system(lookup_expected_answer)
+                .with_system(show_answer_badge)
+                .with_system(draw_axes)
+                .with_system(tween_bars)
+                // Only in puzzles, so the menu keys and mouse leave the camera alone
+                .with_system(camera_follow)
+                .with_system(camera_wheel_zoom)
+                .with_system(camera_drag_pan)
+                .with_system(camera_keys),
+        );
+        app.add_system_set(puzzle.step(SystemSet::on_update(state.clone())));
+        app.add_system_set(puzzle.measure(SystemSet::on_update(state.clone())));
+        app.add_system_set(
+            puzzle.cleanup(
+                SystemSet::on_exit(state.clone())
+                    .with_system(despawn_puzzle_entities)
+                    .with_system(unload_puzzle_input)
+                    .with_system(unload_answers)
+                    .with_system(unload_hud)
+                    .with_system(reset_camera),
+            ),
+        );
+        // Restarting pauses the puzzle state and resumes it straight away
+        app.add_system_set(
+            puzzle.cleanup(
+                SystemSet::on_pause(state.clone())
+                    .with_system(despawn_puzzle_entities)
+                    .with_system(reset_camera),
+            ),
+        );
+        app.add_system_set(
+            puzzle.setup(
+                SystemSet::on_resume(state)
+                    .with_system(setup_controls)
+                    .with_system(setup_hud)
+                    .with_system(setup_event_log)
+                    .with_system(reset_final_answer),
+            ),
+        );
+        app.world
+            .get_resource_or_insert_with(PuzzleRegistry::default)
+            .puzzles
+            .push(info);
+    }
+    app.add_system(show_not_implemented);
+    app
+}
+
+fn return_to_menu(keys: Res<Input<KeyCode>>, mut state: ResMut<State<AOCState>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        // Ignore the key if another transition is already queued this frame
+        let _ = state.set(AOCState::menu());
+    }
+}
+
+#[derive(Component)]
+struct NotImplementedScene;
+
+/// Fallback for a year/day/part state without a registered puzzle, which would otherwise
+/// leave an empty screen. Escape goes back to the menu as in any puzzle.
+fn show_not_implemented(
+    mut commands: Commands,
+    mut state: ResMut<State<AOCState>>,
+    registry: Res<PuzzleRegistry>,
+    keys: Res<Input<KeyCode>>,
+    aoc_font: Res<AocFont>,
+    scenes: Query<Entity, With<NotImplementedScene>>,
+) {
+    let current = state.current().clone();
+    let missing = current != AOCState::menu()
+        && current != AOCState::restart()
+        && !registry.contains(&current);
+    if !missing {
+        for entity in scenes.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        let _ = state.set(AOCState::menu());
+    }
+    if !scenes.is_empty() {
+        return;
+    }
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                format!(
+                    "{} day {} part {} is not implemented yet\nPress Escape to return to the menu",
+                    current.year, current.day, current.part
+                ),
+                TextStyle {
+                    font: aoc_font.font_handle.clone(),
+                    font_size: 40.0,
+                    color: NORMAL_TEXT,
+                },
+                TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center,
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(300.0),
+                    top: Val::Px(300.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(NotImplementedScene);
+}
+
+fn despawn_puzzle_entities(mut commands: Commands, query: Query<Entity, With<PuzzleEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = PuzzleRegistry {
+            puzzles: vec![PuzzleInfo {
+                year: 2018,
+                day: 1,
+                part: 2,
+                title: "Chronal Calibration",
+            }],
+        };
+        let state = AOCState {
+            year: 2018,
+            day: 1,
+            part: 2,
+        };
+        assert_eq!(registry.get(&state).unwrap().title, "Chronal Calibration");
+        assert!(!registry.contains(&AOCState {
+            year: 2018,
+            day: 1,
+            part: 1,
+        }));
+        assert!(registry.any(Some(2018), None, None));
+        assert!(registry.any(None, Some(1), Some(2)));
+        assert!(!registry.any(Some(2018), Some(2), None));
+        assert_eq!(registry.title(2018, 1), Some("Chronal Calibration"));
+        assert_eq!(registry.title(2018, 4), None);
+    }
+}
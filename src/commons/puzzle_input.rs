@@ -0,0 +1,388 @@
+use std::path::Path;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::{TypeUuid, Uuid},
+    utils::BoxedFuture,
+    window::FileDragAndDrop,
+};
+
+use super::{
+    aoc_common::{AOCState, AocFont, InputLines, PuzzleEntity},
+    event_log::EventLog,
+    parse::{split_lines, validate_lines, LineSplitter, LineValidator, ParseError},
+};
+
+pub struct PuzzleInputPlugin;
+
+impl Plugin for PuzzleInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<PuzzleInput>()
+            .init_asset_loader::<PuzzleInputLoader>()
+            .add_system_to_stage(CoreStage::PreUpdate, fill_input_lines)
+            .add_system(show_input_error)
+            .add_system(drop_input_file);
+    }
+}
+
+/// A puzzle input text file, split into lines by the active puzzle's [`InputFormat`]. A
+/// file that cannot be read as text still loads, so the error can be shown in the puzzle's
+/// scene.
+#[derive(Debug)]
+pub struct PuzzleInput {
+    pub bytes: Vec<u8>,
+}
+
+// The derive macro only looks for bevy in the non target specific dependencies
+impl TypeUuid for PuzzleInput {
+    const TYPE_UUID: Uuid = Uuid::from_u128(0x5b1d3a8e_6f0c_4a8e_9d2b_3c7e1f4a9b60);
+}
+
+#[derive(Default)]
+pub struct PuzzleInputLoader;
+
+impl AssetLoader for PuzzleInputLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let bytes = bytes.to_vec();
+            load_context.set_default_asset(LoadedAsset::new(PuzzleInput { bytes }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["txt"]
+    }
+}
+
+/// Where a puzzle's input is read from, picked in the menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The small example from the puzzle description.
+    Sample,
+    /// The full puzzle input shipped in the assets folder.
+    Real,
+    /// A file provided by the user, e.g. with `--input`.
+    Custom,
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Real
+    }
+}
+
+impl InputSource {
+    pub const ALL: [InputSource; 3] = [InputSource::Sample, InputSource::Real, InputSource::Custom];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputSource::Sample => "Sample",
+            InputSource::Real => "Real",
+            InputSource::Custom => "Custom",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|source| source.label() == label)
+    }
+}
+
+/// Input used by the next puzzle that is launched.
+pub struct InputSelection {
+    pub source: InputSource,
+    pub custom_path: Option<String>,
+}
+
+impl InputSelection {
+    pub fn new(custom_path: Option<String>) -> Self {
+        let source = match custom_path {
+            Some(_) => InputSource::Custom,
+            None => InputSource::Real,
+        };
+        Self {
+            source,
+            custom_path,
+        }
+    }
+
+    /// Whether a source can be picked, the custom input only once a file was provided.
+    pub fn has(&self, source: InputSource) -> bool {
+        source != InputSource::Custom || self.custom_path.is_some()
+    }
+
+    /// Name of the input shown in the HUD, the file name for custom inputs.
+    pub fn label(&self) -> String {
+        match (self.source, &self.custom_path) {
+            (InputSource::Custom, Some(custom_path)) => {
+                Path::new(custom_path).file_name().map_or_else(
+                    || custom_path.clone(),
+                    |name| name.to_string_lossy().into_owned(),
+                )
+            }
+            (InputSource::Custom, None) => InputSource::Real.label().to_string(),
+            (source, _) => source.label().to_string(),
+        }
+    }
+
+    /// Falls back to the real input when no custom file has been provided.
+    pub fn path<'a>(&'a self, input_path: &'a str, sample_path: &'a str) -> &'a str {
+        match (self.source, &self.custom_path) {
+            (InputSource::Sample, _) => sample_path,
+            (InputSource::Custom, Some(custom_path)) => custom_path,
+            _ => input_path,
+        }
+    }
+}
+
+/// Input of the active puzzle, inserted when the puzzle is launched.
+pub struct PuzzleInputHandle(pub Handle<PuzzleInput>);
+
+/// Splits the active puzzle's input into lines and checks every line before it is handed
+/// to the puzzle.
+pub struct InputFormat {
+    pub split: LineSplitter,
+    pub validate: LineValidator,
+}
+
+/// First line of the input the active puzzle cannot parse. The puzzle's [`InputLines`] stay
+/// empty while this is set, so the puzzle never sees the bad input.
+pub struct InputError(pub ParseError);
+
+/// Starts loading the input picked in the [`InputSelection`].
+pub fn load_puzzle_input(
+    input_path: String,
+    sample_path: String,
+    splitter: LineSplitter,
+    validator: LineValidator,
+) -> impl FnMut(Commands, Res<AssetServer>, Res<InputSelection>) + Send + Sync + 'static {
+    move |mut commands: Commands,
+          asset_server: Res<AssetServer>,
+          input_selection: Res<InputSelection>| {
+        let path = input_selection.path(&input_path, &sample_path);
+        commands.insert_resource(PuzzleInputHandle(asset_server.load(path)));
+        commands.insert_resource(InputFormat {
+            split: splitter,
+            validate: validator,
+        });
+    }
+}
+
+pub fn unload_puzzle_input(mut commands: Commands) {
+    commands.remove_resource::<PuzzleInputHandle>();
+    commands.remove_resource::<InputFormat>();
+    commands.remove_resource::<InputError>();
+}
+
+fn fill_input_lines(
+    mut commands: Commands,
+    handle: Option<Res<PuzzleInputHandle>>,
+    format: Option<Res<InputFormat>>,
+    error: Option<Res<InputError>>,
+    inputs: Res<Assets<PuzzleInput>>,
+    mut event_log: ResMut<EventLog>,
+    mut query: Query<&mut InputLines>,
+) {
+    if error.is_some() {
+        return;
+    }
+    let input = match handle.and_then(|handle| inputs.get(&handle.0)) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut unloaded = query
+        .iter_mut()
+        .filter(|input_lines| !input_lines.loaded)
+        .peekable();
+    if unloaded.peek().is_none() {
+        return;
+    }
+    let lines = match format {
+        Some(format) => (format.split)(&input.bytes).and_then(|lines| {
+            validate_lines(&lines, format.validate)?;
+            Ok(lines)
+        }),
+        None => split_lines(&input.bytes),
+    };
+    match lines {
+        Ok(lines) => {
+            for mut input_lines in unloaded {
+                *input_lines = InputLines::from_lines(lines.clone());
+            }
+        }
+        Err(error) => {
+            event_log.warn(None, format!("Parse error on {}", error));
+            commands.insert_resource(InputError(error));
+        }
+    }
+}
+
+/// Makes a `.txt` file dropped on the window the custom input. A running puzzle restarts
+/// with it straight away, in the menu it is picked for the next launch.
+fn drop_input_file(
+    mut commands: Commands,
+    mut events: EventReader<FileDragAndDrop>,
+    mut input_selection: ResMut<InputSelection>,
+    mut inputs: ResMut<Assets<PuzzleInput>>,
+    mut state: ResMut<State<AOCState>>,
+    handle: Option<Res<PuzzleInputHandle>>,
+) {
+    for event in events.iter() {
+        let path = match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => path_buf,
+            _ => continue,
+        };
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "txt")
+        {
+            warn!(
+                "ignoring dropped file {}, inputs are .txt files",
+                path.display()
+            );
+            continue;
+        }
+        // Read the file again rather than going through the asset server, which would
+        // return the cached input when the same file is dropped after editing it
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                warn!("could not read {}: {}", path.display(), error);
+                continue;
+            }
+        };
+        info!("using dropped input {}", path.display());
+        input_selection.source = InputSource::Custom;
+        input_selection.custom_path = Some(path.to_string_lossy().into_owned());
+        if handle.is_some() {
+            let input = inputs.add(PuzzleInput { bytes });
+            commands.insert_resource(PuzzleInputHandle(input));
+            commands.remove_resource::<InputError>();
+            // Ignore the drop if another transition is already queued this frame
+            let _ = state.push(AOCState::restart());
+        }
+    }
+}
+
+/// Shows the name of the input the puzzle runs on.
+#[derive(Component)]
+pub struct InputStatus;
+
+pub fn update_input_status(
+    input_selection: Res<InputSelection>,
+    mut query: Query<&mut Text, With<InputStatus>>,
+) {
+    let status = format!("Input: {}", input_selection.label());
+    for mut text in query.iter_mut() {
+        if text.sections[0].value != status {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
+
+#[derive(Component)]
+struct InputErrorPanel;
+
+/// Longest part of the offending line shown in the panel.
+const MAX_ERROR_TEXT: usize = 60;
+
+/// Cuts the text down to [`MAX_ERROR_TEXT`] characters, marking where it was cut.
+fn shorten(text: &str) -> String {
+    let mut short = text.chars().take(MAX_ERROR_TEXT).collect::<String>();
+    if text.chars().nth(MAX_ERROR_TEXT).is_some() {
+        short.push_str("...");
+    }
+    short
+}
+
+fn show_input_error(
+    mut commands: Commands,
+    error: Option<Res<InputError>>,
+    aoc_font: Res<AocFont>,
+    panels: Query<Entity, With<InputErrorPanel>>,
+) {
+    let error = match error {
+        Some(error) if panels.is_empty() => error.0.clone(),
+        _ => return,
+    };
+    let text = shorten(&error.text);
+    let style = TextStyle {
+        font: aoc_font.font_handle.clone(),
+        font_size: 30.0,
+        color: Color::WHITE,
+    };
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(200.0),
+                    top: Val::Px(250.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: Rect::all(Val::Px(20.0)),
+                ..Default::default()
+            },
+            color: Color::rgb(0.5, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    format!("Invalid input on line {}: {}", error.line, error.message),
+                    style.clone(),
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    text,
+                    TextStyle {
+                        color: Color::GOLD,
+                        ..style
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+        })
+        .insert(InputErrorPanel)
+        .insert(PuzzleEntity);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_selection_path() {
+        let mut selection = InputSelection::new(None);
+        assert_eq!(selection.path("real", "sample"), "real");
+        selection.source = InputSource::Sample;
+        assert_eq!(selection.path("real", "sample"), "sample");
+        selection.source = InputSource::Custom;
+        assert_eq!(selection.path("real", "sample"), "real");
+        assert_eq!(selection.label(), "Real");
+
+        let selection = InputSelection::new(Some("/tmp/inputs/custom.txt".to_string()));
+        assert_eq!(selection.source, InputSource::Custom);
+        assert_eq!(selection.path("real", "sample"), "/tmp/inputs/custom.txt");
+        assert_eq!(selection.label(), "custom.txt");
+    }
+
+    #[test]
+    fn test_shorten() {
+        let exact = "é".repeat(MAX_ERROR_TEXT);
+        assert_eq!(shorten(&exact), exact);
+        let long = "é".repeat(MAX_ERROR_TEXT + 1);
+        assert_eq!(shorten(&long), format!("{}...", exact));
+        assert_eq!(shorten("+1"), "+1");
+    }
+}
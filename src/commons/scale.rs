@@ -0,0 +1,207 @@
+/// How data values are spread over a scale's range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScaleKind {
+    Linear,
+    /// Logarithmic in both directions around zero, `sign(v) * log10(1 + |v|)`, so zero and
+    /// negative values stay defined.
+    SymLog,
+}
+
+/// Maps values from a data domain onto a pixel range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale {
+    pub kind: ScaleKind,
+    pub domain: (f32, f32),
+    pub range: (f32, f32),
+    /// Values outside the domain are mapped to the nearest end of the range.
+    pub clamp: bool,
+}
+
+impl Scale {
+    pub fn linear(domain: (f32, f32), range: (f32, f32)) -> Self {
+        Self {
+            kind: ScaleKind::Linear,
+            domain,
+            range,
+            clamp: false,
+        }
+    }
+
+    pub fn symlog(domain: (f32, f32), range: (f32, f32)) -> Self {
+        Self {
+            kind: ScaleKind::SymLog,
+            ..Self::linear(domain, range)
+        }
+    }
+
+    pub fn clamped(self) -> Self {
+        Self {
+            clamp: true,
+            ..self
+        }
+    }
+
+    /// Widens the domain outwards to the closest ticks, so the ends of the axis are labelled.
+    pub fn nice(self, count: usize) -> Self {
+        if self.kind != ScaleKind::Linear {
+            return self;
+        }
+        let step = tick_step(self.domain.0, self.domain.1, count);
+        Self {
+            domain: (
+                (self.domain.0 / step).floor() * step,
+                (self.domain.1 / step).ceil() * step,
+            ),
+            ..self
+        }
+    }
+
+    pub fn map(&self, value: f32) -> f32 {
+        let (start, end) = (self.transform(self.domain.0), self.transform(self.domain.1));
+        let mut t = if start == end {
+            0.5
+        } else {
+            (self.transform(value) - start) / (end - start)
+        };
+        if self.clamp {
+            t = t.clamp(0.0, 1.0);
+        }
+        self.range.0 + t * (self.range.1 - self.range.0)
+    }
+
+    /// Tick values inside the domain, about `count` of them for linear scales and one per
+    /// power of ten for symmetric log scales. A symmetric log domain holding fewer than two
+    /// powers of ten gets linear ticks instead.
+    pub fn ticks(&self, count: usize) -> Vec<f32> {
+        let (min, max) = ordered(self.domain);
+        match self.kind {
+            ScaleKind::Linear => {
+                let step = tick_step(min, max, count);
+                let first = (min / step).ceil() as i64;
+                let last = (max / step).floor() as i64;
+                (first..=last).map(|tick| tick as f32 * step).collect()
+            }
+            ScaleKind::SymLog => {
+                let mut ticks = vec![0.0];
+                let mut power = 1.0;
+                while power <= max.abs().max(min.abs()) {
+                    ticks.push(power);
+                    ticks.push(-power);
+                    power *= 10.0;
+                }
+                ticks.retain(|tick| *tick >= min && *tick <= max);
+                if ticks.len() < 2 {
+                    return Scale::linear(self.domain, self.range).ticks(count);
+                }
+                ticks.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                ticks
+            }
+        }
+    }
+
+    fn transform(&self, value: f32) -> f32 {
+        match self.kind {
+            ScaleKind::Linear => value,
+            ScaleKind::SymLog => value.signum() * (1.0 + value.abs()).log10(),
+        }
+    }
+}
+
+/// Smallest domain holding every value. A constant or empty series is widened by one in
+/// both directions so it does not collapse to a single point.
+pub fn auto_range(values: impl IntoIterator<Item = f32>) -> (f32, f32) {
+    let (min, max) = values
+        .into_iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    if min > max {
+        (-1.0, 1.0)
+    } else if min == max {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+/// Short label for a tick, large values use a k or M suffix.
+pub fn format_tick(value: f32) -> String {
+    let (value, suffix) = match value.abs() {
+        abs if abs >= 1_000_000.0 => (value / 1_000_000.0, "M"),
+        abs if abs >= 10_000.0 => (value / 1_000.0, "k"),
+        _ => (value, ""),
+    };
+    let text = format!("{:.1}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    let text = if text == "-0" { "0" } else { text };
+    format!("{}{}", text, suffix)
+}
+
+fn ordered((a, b): (f32, f32)) -> (f32, f32) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// A 1, 2 or 5 times a power of ten step giving about `count` ticks over the span.
+fn tick_step(min: f32, max: f32, count: usize) -> f32 {
+    let span = (max - min).abs();
+    if span == 0.0 || !span.is_finite() {
+        return 1.0;
+    }
+    let raw = span / count.max(1) as f32;
+    let magnitude = 10f32.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10.0 * magnitude)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_linear() {
+        let scale = Scale::linear((-10.0, 10.0), (0.0, 100.0));
+        assert_eq!(scale.map(-10.0), 0.0);
+        assert_eq!(scale.map(0.0), 50.0);
+        assert_eq!(scale.map(20.0), 150.0);
+        assert_eq!(scale.clamped().map(20.0), 100.0);
+        assert_eq!(scale.ticks(4), [-10.0, -5.0, 0.0, 5.0, 10.0]);
+
+        let scale = Scale::linear((3.0, 97.0), (0.0, 1.0)).nice(5);
+        assert_eq!(scale.domain, (0.0, 100.0));
+    }
+
+    #[test]
+    fn test_symlog() {
+        let scale = Scale::symlog((-999.0, 999.0), (-3.0, 3.0));
+        assert_eq!(scale.map(0.0), 0.0);
+        assert!((scale.map(99.0) - 2.0).abs() < 1e-5);
+        assert!((scale.map(-9.0) + 1.0).abs() < 1e-5);
+        assert_eq!(scale.ticks(5), [-100.0, -10.0, -1.0, 0.0, 1.0, 10.0, 100.0]);
+        let scale = Scale::symlog((20.0, 90.0), (0.0, 1.0));
+        assert_eq!(scale.ticks(4), [20.0, 40.0, 60.0, 80.0]);
+    }
+
+    #[test]
+    fn test_auto_range() {
+        assert_eq!(auto_range(vec![3.0, -2.0, 1.0]), (-2.0, 3.0));
+        assert_eq!(auto_range(vec![5.0, 5.0]), (4.0, 6.0));
+        assert_eq!(auto_range(vec![]), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_format_tick() {
+        assert_eq!(format_tick(0.0), "0");
+        assert_eq!(format_tick(-0.0), "0");
+        assert_eq!(format_tick(2.5), "2.5");
+        assert_eq!(format_tick(-400.0), "-400");
+        assert_eq!(format_tick(60000.0), "60k");
+        assert_eq!(format_tick(1_500_000.0), "1.5M");
+    }
+}
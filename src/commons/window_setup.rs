@@ -3,8 +3,10 @@ use super::{
     constants::*,
 };
 use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
-    render::camera::{Camera, CameraProjection, OrthographicProjection},
+    render::camera::OrthographicProjection,
+    text::Text2dSize,
 };
 pub struct WindowSetup;
 
@@ -19,13 +21,15 @@ impl Plugin for WindowSetup {
         });
         app.add_startup_system(camera_setup);
         app.insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.1)));
-        app.add_system(camera_zoom);
+        app.init_resource::<CameraControl>();
+        app.add_system_to_stage(CoreStage::PostUpdate, update_content_bounds);
         //app.add_system(scale_with_zoom.system());
         app.insert_resource(BorderSize {
             max_x: WINDOW_WIDTH / 2.0,
             max_y: WINDOW_HEIGHT / 2.0,
             current_x: 0.0,
             current_y: 0.0,
+            center: Vec2::ZERO,
         });
     }
 }
@@ -34,50 +38,302 @@ fn camera_setup(mut commands: Commands) {
     commands
         .spawn_bundle(OrthographicCameraBundle::new_2d())
         .insert(AOCName("AOC Camera".to_string()));
+    commands.spawn_bundle(UiCameraBundle::default());
 }
 
-fn camera_zoom(
+/// How the camera is moved while a puzzle runs. While following, the view is fitted to the
+/// puzzle's [`BorderSize`] and centred on it every frame. Panning or zooming by hand turns
+/// following off, and the fit key turns it back on.
+pub struct CameraControl {
+    pub follow: bool,
+    /// Cursor position of the last frame of a mouse drag.
+    drag_position: Option<Vec2>,
+}
+
+impl Default for CameraControl {
+    fn default() -> Self {
+        Self {
+            follow: true,
+            drag_position: None,
+        }
+    }
+}
+
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 100.0;
+/// Zoom factor applied per line scrolled.
+const WHEEL_ZOOM: f32 = 1.1;
+/// Pixels per second panned with the keyboard, at a zoom of 1.
+const PAN_SPEED: f32 = 600.0;
+
+type CameraQuery<'w, 's> =
+    Query<'w, 's, (&'static mut OrthographicProjection, &'static mut Transform), With<AOCName>>;
+
+pub fn reset_camera(
+    mut border_size: ResMut<BorderSize>,
+    mut camera_control: ResMut<CameraControl>,
+    mut query: CameraQuery,
+) {
+    border_size.reset();
+    *camera_control = CameraControl::default();
+    let (mut projection, mut transform) = query.single_mut();
+    projection.scale = 1.0;
+    transform.translation.x = 0.0;
+    transform.translation.y = 0.0;
+}
+
+/// Zoom at which everything inside the border is visible, never zooming in past 1.
+fn fit_scale(border_size: &BorderSize) -> f32 {
+    (border_size.current_x / border_size.max_x)
+        .max(border_size.current_y / border_size.max_y)
+        .max(1.0)
+}
+
+pub fn camera_follow(
     border_size: Res<BorderSize>,
-    window: Res<WindowDescriptor>,
-    mut query: Query<(&mut OrthographicProjection, &mut Camera), With<AOCName>>,
+    camera_control: Res<CameraControl>,
+    mut query: CameraQuery,
+) {
+    if !camera_control.follow {
+        return;
+    }
+    let (mut projection, mut transform) = query.single_mut();
+    let scale = fit_scale(&border_size);
+    // Only write on changes, the camera recomputes its projection whenever it is touched
+    if projection.scale != scale {
+        projection.scale = scale;
+    }
+    let center = border_size.center;
+    if transform.translation.truncate() != center {
+        transform.translation.x = center.x;
+        transform.translation.y = center.y;
+    }
+}
+
+/// Zooms around the cursor, keeping the world point under it in place. The wheel is left
+/// to the UI while the cursor is over it, so the event log can scroll.
+pub fn camera_wheel_zoom(
+    mut wheel_events: EventReader<MouseWheel>,
+    windows: Res<Windows>,
+    interactions: Query<&Interaction>,
+    mut camera_control: ResMut<CameraControl>,
+    mut query: CameraQuery,
 ) {
-    let (mut projection, mut camera) = query.single_mut();
-    if border_size.current_x > border_size.max_x {
-        projection.scale = 1.0
-            + ((border_size.current_x as f32 - border_size.max_x as f32)
-                / border_size.max_x as f32);
-    } else if border_size.current_y > border_size.max_y {
-        projection.scale = 1.0
-            + ((border_size.current_y as f32 - border_size.max_y as f32)
-                / border_size.max_y as f32);
+    let lines = wheel_events
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum::<f32>();
+    let on_ui = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    if lines == 0.0 || on_ui {
+        return;
+    }
+    let (mut projection, mut transform) = query.single_mut();
+    let scale = (projection.scale * WHEEL_ZOOM.powf(-lines)).clamp(MIN_ZOOM, MAX_ZOOM);
+    if let Some(offset) = windows.get_primary().and_then(cursor_offset) {
+        let world = transform.translation.truncate() + offset * projection.scale;
+        let translation = world - offset * scale;
+        transform.translation.x = translation.x;
+        transform.translation.y = translation.y;
     }
-    projection.update(window.width, window.height);
-    camera.projection_matrix = projection.get_projection_matrix();
+    projection.scale = scale;
+    camera_control.follow = false;
 }
 
-/*fn scale_with_zoom(
-    border_size: Res<BorderSize>,
-    mut query: Query<&mut Sprite, With<ScalableObject>>,
+/// Cursor position relative to the centre of the window.
+fn cursor_offset(window: &Window) -> Option<Vec2> {
+    window
+        .cursor_position()
+        .map(|cursor| cursor - Vec2::new(window.width(), window.height()) / 2.0)
+}
+
+/// Pans while the left mouse button is held, unless the press started on a UI element.
+pub fn camera_drag_pan(
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    interactions: Query<&Interaction>,
+    mut camera_control: ResMut<CameraControl>,
+    mut query: CameraQuery,
 ) {
-    for mut sprite in query.iter_mut() {
-        if border_size.current_x > border_size.max_x {
-            sprite.size[0] = 1.0
-                + ((border_size.current_x as f32 - border_size.max_x as f32)
-                    / border_size.max_x as f32);
-            sprite.size[1] = 1.0
-                + ((border_size.current_x as f32 - border_size.max_x as f32)
-                    / border_size.max_x as f32);
-            sprite.size[0] *= 10.0;
-            sprite.size[1] *= 10.0;
-        } else if border_size.current_y > border_size.max_y {
-            sprite.size[0] = 1.0
-                + ((border_size.current_y as f32 - border_size.max_y as f32)
-                    / border_size.max_y as f32);
-            sprite.size[1] = 1.0
-                + ((border_size.current_y as f32 - border_size.max_y as f32)
-                    / border_size.max_y as f32);
-            sprite.size[0] *= 10.0;
-            sprite.size[1] *= 10.0;
+    let cursor = windows
+        .get_primary()
+        .and_then(|window| window.cursor_position());
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        let on_ui = interactions
+            .iter()
+            .any(|interaction| *interaction != Interaction::None);
+        camera_control.drag_position = if on_ui { None } else { cursor };
+        return;
+    }
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        camera_control.drag_position = None;
+        return;
+    }
+    if let (Some(last), Some(cursor)) = (camera_control.drag_position, cursor) {
+        if last != cursor {
+            let (projection, mut transform) = query.single_mut();
+            let delta = (cursor - last) * projection.scale;
+            transform.translation.x -= delta.x;
+            transform.translation.y -= delta.y;
+            camera_control.drag_position = Some(cursor);
+            camera_control.follow = false;
         }
     }
-}*/
+}
+
+/// WASD or shift and the arrow keys pan, F fits the view to the content and follows it again.
+pub fn camera_keys(
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut camera_control: ResMut<CameraControl>,
+    mut query: CameraQuery,
+) {
+    if keys.just_pressed(KeyCode::F) {
+        camera_control.follow = true;
+    }
+    let shift = keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift);
+    let bindings = [
+        (KeyCode::A, KeyCode::Left, Vec2::new(-1.0, 0.0)),
+        (KeyCode::D, KeyCode::Right, Vec2::new(1.0, 0.0)),
+        (KeyCode::W, KeyCode::Up, Vec2::new(0.0, 1.0)),
+        (KeyCode::S, KeyCode::Down, Vec2::new(0.0, -1.0)),
+    ];
+    let direction = bindings
+        .iter()
+        .filter(|(key, arrow, _)| keys.pressed(*key) || (shift && keys.pressed(*arrow)))
+        .fold(Vec2::ZERO, |sum, (_, _, direction)| sum + *direction);
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let (projection, mut transform) = query.single_mut();
+    let delta = direction * PAN_SPEED * projection.scale * time.delta_seconds();
+    transform.translation.x += delta.x;
+    transform.translation.y += delta.y;
+    camera_control.follow = false;
+}
+
+/// Space kept between the content and the edge of the window when fitting.
+const CONTENT_MARGIN: f32 = 20.0;
+
+/// World space rectangle covered by a sprite.
+fn sprite_rect(transform: &GlobalTransform, sprite: &Sprite) -> Option<(Vec2, Vec2)> {
+    let half_size = sprite.custom_size? * transform.scale.truncate() / 2.0;
+    let center = transform.translation.truncate();
+    Some((center - half_size, center + half_size))
+}
+
+/// World space rectangle covered by a text, placed the same way the text renderer aligns it.
+fn text_rect(transform: &GlobalTransform, alignment: TextAlignment, size: Vec2) -> (Vec2, Vec2) {
+    let size = size * transform.scale.truncate();
+    let offset_x = match alignment.horizontal {
+        HorizontalAlign::Left => 0.0,
+        HorizontalAlign::Center => -size.x / 2.0,
+        HorizontalAlign::Right => -size.x,
+    };
+    let offset_y = match alignment.vertical {
+        VerticalAlign::Top => -size.y,
+        VerticalAlign::Center => -size.y / 2.0,
+        VerticalAlign::Bottom => 0.0,
+    };
+    let min = transform.translation.truncate() + Vec2::new(offset_x, offset_y);
+    (min, min + size)
+}
+
+/// Smallest rectangle holding all of the rectangles, none if there are none.
+fn content_bounds(rects: impl Iterator<Item = (Vec2, Vec2)>) -> Option<(Vec2, Vec2)> {
+    rects.reduce(|(bounds_min, bounds_max), (min, max)| (bounds_min.min(min), bounds_max.max(max)))
+}
+
+/// Keeps [`BorderSize`] covering every [`ScalableObject`] sprite and text, so the follow mode
+/// frames each puzzle without it having to size the border itself.
+fn update_content_bounds(
+    mut border_size: ResMut<BorderSize>,
+    sprites: Query<(&GlobalTransform, &Sprite), With<ScalableObject>>,
+    texts: Query<(&GlobalTransform, &Text, &Text2dSize), With<ScalableObject>>,
+) {
+    let sprite_rects = sprites
+        .iter()
+        .filter_map(|(transform, sprite)| sprite_rect(transform, sprite));
+    // Blank texts, such as empty cells of a grid, take no room on screen
+    let text_rects = texts
+        .iter()
+        .filter(|(_, text, _)| {
+            text.sections
+                .iter()
+                .any(|section| !section.value.trim().is_empty())
+        })
+        .map(|(transform, text, size)| {
+            let size = Vec2::new(size.size.width, size.size.height);
+            text_rect(transform, text.alignment, size)
+        });
+    let (current_x, current_y, center) = match content_bounds(sprite_rects.chain(text_rects)) {
+        Some((min, max)) => {
+            let half_size = (max - min) / 2.0;
+            (
+                half_size.x + CONTENT_MARGIN,
+                half_size.y + CONTENT_MARGIN,
+                (min + max) / 2.0,
+            )
+        }
+        None => (0.0, 0.0, Vec2::ZERO),
+    };
+    // Only write on changes, so change detection on the border keeps working
+    if border_size.current_x != current_x
+        || border_size.current_y != current_y
+        || border_size.center != center
+    {
+        border_size.current_x = current_x;
+        border_size.current_y = current_y;
+        border_size.center = center;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fit_scale() {
+        let mut border_size = BorderSize {
+            max_x: 640.0,
+            max_y: 360.0,
+            current_x: 0.0,
+            current_y: 0.0,
+            center: Vec2::ZERO,
+        };
+        assert_eq!(fit_scale(&border_size), 1.0);
+        border_size.current_x = 1280.0;
+        border_size.current_y = 540.0;
+        assert_eq!(fit_scale(&border_size), 2.0);
+        border_size.current_y = 1080.0;
+        assert_eq!(fit_scale(&border_size), 3.0);
+    }
+
+    #[test]
+    fn test_content_bounds() {
+        let transform = GlobalTransform::from_xyz(100.0, -50.0, 0.0);
+        let sprite = Sprite {
+            custom_size: Some(Vec2::new(40.0, 20.0)),
+            ..Default::default()
+        };
+        let sprite = sprite_rect(&transform, &sprite).unwrap();
+        assert_eq!(sprite, (Vec2::new(80.0, -60.0), Vec2::new(120.0, -40.0)));
+
+        let alignment = TextAlignment {
+            vertical: VerticalAlign::Top,
+            horizontal: HorizontalAlign::Right,
+        };
+        let text = text_rect(&transform, alignment, Vec2::new(300.0, 10.0));
+        assert_eq!(text, (Vec2::new(-200.0, -60.0), Vec2::new(100.0, -50.0)));
+
+        assert_eq!(
+            content_bounds(vec![sprite, text].into_iter()),
+            Some((Vec2::new(-200.0, -60.0), Vec2::new(120.0, -40.0)))
+        );
+        assert_eq!(content_bounds(std::iter::empty()), None);
+    }
+}
@@ -2,28 +2,69 @@ mod aoc_2018;
 mod commons;
 mod menu;
 use bevy::prelude::*;
+use commons::answers::AnswersPlugin;
 use commons::aoc_common::{AOCState, AocFont};
 
+use commons::cli::CliArgs;
+use commons::event_log::EventLogPlugin;
 use commons::fps::FpsPlugin;
+#[cfg(not(target_arch = "wasm32"))]
+use commons::headless;
+use commons::playback::PlaybackPlugin;
+use commons::puzzle;
+use commons::puzzle_input::{InputSelection, PuzzleInputPlugin};
 use commons::window_setup::WindowSetup;
 
-fn setup() -> App {
+fn setup(cli_args: CliArgs) -> App {
     let mut app = App::new();
     app.add_startup_system(AocFont::setup_font_resource.system().label("font_init"));
     app.add_plugin(FpsPlugin);
     app.add_plugin(WindowSetup);
+    app.add_plugin(PlaybackPlugin);
     app.add_plugins(DefaultPlugins);
-    app.add_state(AOCState {
-        year: 0,
-        day: 0,
-        part: 1,
-    });
+    app.add_plugin(PuzzleInputPlugin);
+    app.add_plugin(AnswersPlugin);
+    app.add_plugin(EventLogPlugin);
+    app.insert_resource(InputSelection::new(cli_args.input.clone()));
+    app.add_state(cli_args.start.clone().unwrap_or_else(AOCState::menu));
+    app.insert_resource(cli_args);
     app = menu::setup_app(app);
-    app = aoc_2018::setup_app(app);
+    app = puzzle::register_puzzles(app, aoc_2018::puzzles());
     app
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() {
-    let mut app = setup();
+    let cli_args = match CliArgs::from_env() {
+        Ok(cli_args) => cli_args,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    };
+    if let Some(problem) = &cli_args.solve {
+        let puzzles = aoc_2018::puzzles();
+        if let Err(error) = headless::solve(&puzzles, problem, cli_args.input.as_deref()) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    let mut app = setup(cli_args);
+    app.run();
+}
+
+/// Exiting panics on the web, so a bad query string opens the menu with a warning instead.
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    let (cli_args, error) = match CliArgs::from_env() {
+        Ok(cli_args) => (cli_args, None),
+        Err(error) => (CliArgs::default(), Some(error)),
+    };
+    let mut app = setup(cli_args);
+    if let Some(error) = error {
+        // Logged once the log plugin is set up, it goes to the browser console
+        app.add_startup_system(move || warn!("ignoring the query string: {}", error));
+    }
     app.run();
 }
@@ -1,70 +1,93 @@
 use crate::commons::{
     aoc_common::{AOCName, AOCState, AocFont},
-    constants::{DAYS, PARTS, WINDOW_HEIGHT, YEARS},
+    cli::CliArgs,
+    constants::{
+        DAYS, DISABLED_BUTTON, DISABLED_TEXT, HOVERED_BUTTON, NORMAL_BUTTON, NORMAL_TEXT, PARTS,
+        PRESSED_BUTTON, SELECTED_BUTTON, WINDOW_HEIGHT, YEARS,
+    },
+    puzzle::PuzzleRegistry,
+    puzzle_input::{InputSelection, InputSource},
 };
 use bevy::prelude::*;
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
-const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
-
 pub fn setup_app(mut app: App) -> App {
+    app.add_system_set(SystemSet::on_enter(AOCState::menu()).with_system(setup_menu.system()));
     app.add_system_set(
-        SystemSet::on_enter(AOCState {
-            year: 0,
-            day: 0,
-            part: 1,
-        })
-        .with_system(setup_menu.system()),
-    );
-    app.add_system_set(
-        SystemSet::on_update(AOCState {
-            year: 0,
-            day: 0,
-            part: 1,
-        })
-        .with_system(menu)
-        .with_system(update_launch_botton),
-    );
-    app.add_system_set(
-        SystemSet::on_exit(AOCState {
-            year: 0,
-            day: 0,
-            part: 1,
-        })
-        .with_system(cleanup_menu.system()),
+        SystemSet::on_update(AOCState::menu())
+            .with_system(menu)
+            .with_system(menu_keys)
+            .with_system(update_launch_botton)
+            .with_system(update_button_availability)
+            .with_system(update_button_colors)
+            .with_system(highlight_focused_header)
+            .with_system(update_puzzle_title)
+            .with_system(pick_dropped_input),
     );
+    app.add_system_set(SystemSet::on_exit(AOCState::menu()).with_system(cleanup_menu.system()));
     app
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
 enum MenuButtonType {
     Year,
     Day,
     Part,
+    Input,
     Launch,
 }
 
+/// Marks a menu button whose year, day or part has no registered puzzle for the current
+/// selection. Disabled buttons are greyed out and ignore clicks.
+#[derive(Component)]
+struct Disabled;
+
+/// Title of the selected day, shown above the launch button.
+#[derive(Component)]
+struct MenuTitle;
+
+/// Header of the column at this index of [`COLUMNS`].
+#[derive(Component)]
+struct MenuHeader(usize);
+
+/// Columns moved between with Left/Right or Tab, in screen order.
+const COLUMNS: [MenuButtonType; 4] = [
+    MenuButtonType::Year,
+    MenuButtonType::Day,
+    MenuButtonType::Part,
+    MenuButtonType::Input,
+];
+
+/// Selected year, day, part and input, zero while not picked yet.
+type Problem = (u16, u8, u8, InputSource);
+
 #[derive(Default)]
 struct MenuData {
     buttons: Vec<Entity>,
-    problem: (u16, u8, u8),
+    problem: Problem,
+    /// Index in [`COLUMNS`] of the column the Up/Down keys move in.
+    focus: usize,
+    /// Day typed with the number keys so far and when, see [`DIGIT_TIMEOUT`].
+    typed_day: Option<(u16, f64)>,
 }
 
 impl MenuData {
-    pub fn new(
-        commands: &mut Commands,
-        aoc_font: &Res<AocFont>,
-    ) -> Self {
+    pub fn new(commands: &mut Commands, aoc_font: &Res<AocFont>) -> Self {
         let mut menu = Self::default();
         let year_header = commands
             .spawn_bundle(Self::create_text_bundle("Year", (150.0, 0.0), aoc_font))
+            .insert(MenuHeader(0))
             .id();
         let day_header = commands
             .spawn_bundle(Self::create_text_bundle("Day", (500.0, 0.0), aoc_font))
+            .insert(MenuHeader(1))
             .id();
         let part_header = commands
             .spawn_bundle(Self::create_text_bundle("Part", (950.0, 0.0), aoc_font))
+            .insert(MenuHeader(2))
+            .id();
+        let input_header = commands
+            .spawn_bundle(Self::create_text_bundle("Input", (1125.0, 0.0), aoc_font))
+            .insert(MenuHeader(3))
             .id();
         let launch_button = Self::create_launch_button(
             (1000.0, WINDOW_HEIGHT / 2.0),
@@ -75,10 +98,40 @@ impl MenuData {
         menu.buttons.push(year_header);
         menu.buttons.push(day_header);
         menu.buttons.push(part_header);
+        menu.buttons.push(input_header);
+        let title = commands
+            .spawn_bundle(Self::create_title_bundle(aoc_font))
+            .insert(MenuTitle)
+            .id();
         menu.buttons.push(launch_button);
+        menu.buttons.push(title);
         menu
     }
 
+    fn create_title_bundle(aoc_font: &Res<AocFont>) -> TextBundle {
+        TextBundle {
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: aoc_font.font_handle.clone(),
+                    font_size: 30.0,
+                    color: Color::GOLD,
+                },
+                Default::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(30.0),
+                    bottom: Val::Px(WINDOW_HEIGHT / 2.0 + 270.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
     fn create_text_bundle(
         text_value: &str,
         pos: (f32, f32),
@@ -120,13 +173,8 @@ impl MenuData {
         aoc_font: &Res<AocFont>,
         pos: (f32, f32),
     ) {
-        let button_entity = Self::create_button_entity(
-            button_text,
-            pos,
-            commands,
-            aoc_font,
-            button_type,
-        );
+        let button_entity =
+            Self::create_button_entity(button_text, pos, commands, aoc_font, button_type);
         self.buttons.push(button_entity);
     }
 
@@ -260,6 +308,22 @@ impl MenuData {
                                         color: Color::GOLD,
                                     },
                                 },
+                                TextSection {
+                                    value: "\nInput: ".to_string(),
+                                    style: TextStyle {
+                                        font: aoc_font.font_handle.clone(),
+                                        font_size: 40.0,
+                                        color: Color::rgb(0.9, 0.9, 0.9),
+                                    },
+                                },
+                                TextSection {
+                                    value: "".to_string(),
+                                    style: TextStyle {
+                                        font: aoc_font.font_handle.clone(),
+                                        font_size: 40.0,
+                                        color: Color::GOLD,
+                                    },
+                                },
                             ],
                             alignment: TextAlignment {
                                 vertical: VerticalAlign::Center,
@@ -288,9 +352,18 @@ fn cleanup_vec_entity(commands: &mut Commands, buttons: &[Entity]) {
 fn setup_menu(
     mut commands: Commands,
     aoc_font: Res<AocFont>,
+    input_selection: Res<InputSelection>,
+    cli_args: Res<CliArgs>,
+    previous_menu: Option<Res<MenuData>>,
 ) {
-    commands.spawn_bundle(UiCameraBundle::default());
     let mut menu_data = MenuData::new(&mut commands, &aoc_font);
+    menu_data.problem.3 = input_selection.source;
+    // Keep the last selection when coming back from a puzzle
+    if let Some(previous_menu) = previous_menu {
+        menu_data.problem = previous_menu.problem;
+    } else if let Some(start) = cli_args.start.as_ref() {
+        menu_data.problem = (start.year, start.day, start.part, input_selection.source);
+    }
     for (index, year) in YEARS.iter().enumerate() {
         menu_data.insert_button(
             &year.to_string(),
@@ -321,71 +394,492 @@ fn setup_menu(
             (900.0, 50.0 * (index + 1) as f32),
         );
     }
+    for (index, source) in InputSource::ALL.iter().enumerate() {
+        menu_data.insert_button(
+            source.label(),
+            MenuButtonType::Input,
+            &mut commands,
+            &aoc_font,
+            (1100.0, 50.0 * (index + 1) as f32),
+        );
+    }
 
     commands.insert_resource(menu_data);
 }
 
+type MenuButtonQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Interaction,
+        &'static MenuButtonType,
+        &'static Children,
+        Option<&'static Disabled>,
+    ),
+    (Changed<Interaction>, With<Button>),
+>;
+
 fn menu(
     mut state: ResMut<State<AOCState>>,
     mut menu_selection: ResMut<MenuData>,
-    mut query_parent: Query<
-        (
-            &Interaction,
-            &mut UiColor,
-            &MenuButtonType,
-            &Children,
-        ),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut input_selection: ResMut<InputSelection>,
+    registry: Res<PuzzleRegistry>,
+    query_parent: MenuButtonQuery,
     query_child: Query<&Text>,
 ) {
-    for (interaction, mut color, name, children) in query_parent.iter_mut() {
+    for (interaction, name, children, disabled) in query_parent.iter() {
+        if disabled.is_some() || *interaction != Interaction::Clicked {
+            continue;
+        }
         let mut value: &str = "0";
         for &child in children.iter() {
             value = &query_child.get(child).unwrap().sections[0].value;
         }
 
-        match (*interaction, &name) {
-            (Interaction::Clicked, MenuButtonType::Launch) => {
-                *color = PRESSED_BUTTON.into();
-                println!("launching state: {:?}", menu_selection.problem);
-                state
-                    .set(AOCState {
-                        year: menu_selection.problem.0,
-                        day: menu_selection.problem.1,
-                        part: menu_selection.problem.2,
-                    })
-                    .unwrap();
-            }
-            (Interaction::Clicked, MenuButtonType::Year) => {
-                *color = PRESSED_BUTTON.into();
-                menu_selection.problem.0 = value.parse::<u16>().unwrap();
-            }
-            (Interaction::Clicked, MenuButtonType::Day) => {
-                *color = PRESSED_BUTTON.into();
-                menu_selection.problem.1 = value.parse::<u8>().unwrap();
+        match name {
+            MenuButtonType::Launch => {
+                launch(&menu_selection, &mut input_selection, &mut state, &registry)
             }
-            (Interaction::Clicked, MenuButtonType::Part) => {
-                *color = PRESSED_BUTTON.into();
-                menu_selection.problem.2 = value.parse::<u8>().unwrap();
-            }
-            (Interaction::Hovered, _) => {
-                *color = HOVERED_BUTTON.into();
-            }
-            (Interaction::None, _) => {
-                *color = NORMAL_BUTTON.into();
+            _ => {
+                select(&mut menu_selection.problem, name, value);
+                if let Some(column) = COLUMNS.iter().position(|column| column == name) {
+                    menu_selection.focus = column;
+                }
             }
         }
     }
 }
 
+fn launch(
+    menu_selection: &MenuData,
+    input_selection: &mut InputSelection,
+    state: &mut State<AOCState>,
+    registry: &PuzzleRegistry,
+) {
+    let selected = AOCState {
+        year: menu_selection.problem.0,
+        day: menu_selection.problem.1,
+        part: menu_selection.problem.2,
+    };
+    if registry.contains(&selected) {
+        info!("launching state: {:?}", menu_selection.problem);
+        input_selection.source = menu_selection.problem.3;
+        // Ignore a second launch from a click and Enter in the same frame
+        let _ = state.set(selected);
+    } else {
+        warn!("no puzzle registered for: {:?}", menu_selection.problem);
+    }
+}
+
+fn select(problem: &mut Problem, button_type: &MenuButtonType, value: &str) {
+    match button_type {
+        MenuButtonType::Year => problem.0 = value.parse().unwrap_or(problem.0),
+        MenuButtonType::Day => problem.1 = value.parse().unwrap_or(problem.1),
+        MenuButtonType::Part => problem.2 = value.parse().unwrap_or(problem.2),
+        MenuButtonType::Input => problem.3 = InputSource::from_label(value).unwrap_or(problem.3),
+        MenuButtonType::Launch => {}
+    }
+}
+
+fn is_selected(button_type: &MenuButtonType, value: &str, problem: Problem) -> bool {
+    match button_type {
+        MenuButtonType::Year => value == problem.0.to_string(),
+        MenuButtonType::Day => value == problem.1.to_string(),
+        MenuButtonType::Part => value == problem.2.to_string(),
+        MenuButtonType::Input => value == problem.3.label(),
+        MenuButtonType::Launch => false,
+    }
+}
+
+/// Button labels of a column, bottom to top as they are laid out.
+fn column_values(button_type: &MenuButtonType) -> Vec<String> {
+    match button_type {
+        MenuButtonType::Year => YEARS.iter().map(|year| year.to_string()).collect(),
+        MenuButtonType::Day => DAYS.iter().map(|day| day.to_string()).collect(),
+        MenuButtonType::Part => PARTS.iter().map(|part| part.to_string()).collect(),
+        MenuButtonType::Input => InputSource::ALL
+            .iter()
+            .map(|source| source.label().to_string())
+            .collect(),
+        MenuButtonType::Launch => Vec::new(),
+    }
+}
+
+/// Next available value of a column after the selected one, `step` buttons up or down.
+/// Without a selection Up starts from the bottom and Down from the top.
+fn step_selection(
+    button_type: &MenuButtonType,
+    step: isize,
+    problem: Problem,
+    registry: &PuzzleRegistry,
+    input_selection: &InputSelection,
+) -> Option<String> {
+    let values = column_values(button_type);
+    let mut index = match values
+        .iter()
+        .position(|value| is_selected(button_type, value, problem))
+    {
+        Some(index) => index as isize,
+        None if step > 0 => -1,
+        None => values.len() as isize,
+    };
+    loop {
+        index += step;
+        let value = values.get(usize::try_from(index).ok()?)?;
+        if is_available(button_type, value, problem, registry, input_selection) {
+            return Some(value.clone());
+        }
+    }
+}
+
+/// Seconds within which a second number key extends the typed day, 1 then 2 picks day 12.
+const DIGIT_TIMEOUT: f64 = 0.8;
+
+fn digit_key(key: &KeyCode) -> Option<u16> {
+    let digits = [
+        (KeyCode::Key0, KeyCode::Numpad0),
+        (KeyCode::Key1, KeyCode::Numpad1),
+        (KeyCode::Key2, KeyCode::Numpad2),
+        (KeyCode::Key3, KeyCode::Numpad3),
+        (KeyCode::Key4, KeyCode::Numpad4),
+        (KeyCode::Key5, KeyCode::Numpad5),
+        (KeyCode::Key6, KeyCode::Numpad6),
+        (KeyCode::Key7, KeyCode::Numpad7),
+        (KeyCode::Key8, KeyCode::Numpad8),
+        (KeyCode::Key9, KeyCode::Numpad9),
+    ];
+    digits
+        .iter()
+        .position(|(key_code, numpad_code)| key == key_code || key == numpad_code)
+        .map(|digit| digit as u16)
+}
+
+/// Day picked by typing `digit`, appended to the day typed just before when that still
+/// gives a valid day.
+fn type_day_digit(typed_day: Option<(u16, f64)>, digit: u16, now: f64) -> u16 {
+    match typed_day {
+        Some((first, at))
+            if now - at < DIGIT_TIMEOUT && first * 10 + digit <= DAYS.len() as u16 =>
+        {
+            first * 10 + digit
+        }
+        _ => digit,
+    }
+}
+
+fn menu_keys(
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut state: ResMut<State<AOCState>>,
+    mut menu_selection: ResMut<MenuData>,
+    mut input_selection: ResMut<InputSelection>,
+    registry: Res<PuzzleRegistry>,
+) {
+    let shift = keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift);
+    // Shift and the arrows pan the camera, so the arrows only move the selection without it
+    let arrow = |key| !shift && keys.just_pressed(key);
+    let tab = keys.just_pressed(KeyCode::Tab);
+    if arrow(KeyCode::Right) || (tab && !shift) {
+        menu_selection.focus = (menu_selection.focus + 1) % COLUMNS.len();
+    }
+    if arrow(KeyCode::Left) || (tab && shift) {
+        menu_selection.focus = (menu_selection.focus + COLUMNS.len() - 1) % COLUMNS.len();
+    }
+    let step = match (arrow(KeyCode::Up), arrow(KeyCode::Down)) {
+        (true, false) => Some(1),
+        (false, true) => Some(-1),
+        _ => None,
+    };
+    if let Some(step) = step {
+        let column = COLUMNS[menu_selection.focus];
+        let problem = menu_selection.problem;
+        if let Some(value) = step_selection(&column, step, problem, &registry, &input_selection) {
+            select(&mut menu_selection.problem, &column, &value);
+        }
+    }
+    if let Some(digit) = keys.get_just_pressed().find_map(digit_key) {
+        let now = time.seconds_since_startup();
+        let day = type_day_digit(menu_selection.typed_day, digit, now);
+        menu_selection.typed_day = Some((day, now));
+        let problem = menu_selection.problem;
+        let available = DAYS.iter().find(|valid_day| {
+            **valid_day as u16 == day
+                && is_available(
+                    &MenuButtonType::Day,
+                    &valid_day.to_string(),
+                    problem,
+                    &registry,
+                    &input_selection,
+                )
+        });
+        if let Some(day) = available {
+            menu_selection.problem.1 = *day;
+            menu_selection.focus = 1;
+        }
+    }
+    if keys.just_pressed(KeyCode::Return) || keys.just_pressed(KeyCode::NumpadEnter) {
+        launch(&menu_selection, &mut input_selection, &mut state, &registry);
+    }
+}
+
 fn update_launch_botton(
     menu_selection: ResMut<MenuData>,
     mut query: Query<&mut Text, With<AOCName>>,
 ) {
     let mut text = query.single_mut();
-    let (year, day, month) = menu_selection.problem;
+    let (year, day, month, source) = menu_selection.problem;
     text.sections[2].value = year.to_string();
     text.sections[4].value = day.to_string();
     text.sections[6].value = month.to_string();
+    text.sections[8].value = source.label().to_string();
+}
+
+/// Whether a button leads to a registered puzzle given the rest of the selection. Parts of
+/// the selection that are not picked yet match any puzzle. The custom input is only
+/// available once a file was given with `--input` or dropped on the window.
+fn is_available(
+    button_type: &MenuButtonType,
+    value: &str,
+    problem: Problem,
+    registry: &PuzzleRegistry,
+    input_selection: &InputSelection,
+) -> bool {
+    let picked_year = Some(problem.0).filter(|year| *year != 0);
+    let picked_day = Some(problem.1).filter(|day| *day != 0);
+    match button_type {
+        MenuButtonType::Year => registry.any(value.parse().ok(), None, None),
+        MenuButtonType::Day => registry.any(picked_year, value.parse().ok(), None),
+        MenuButtonType::Part => registry.any(picked_year, picked_day, value.parse().ok()),
+        MenuButtonType::Input => {
+            InputSource::from_label(value).map_or(false, |source| input_selection.has(source))
+        }
+        MenuButtonType::Launch => {
+            input_selection.has(problem.3)
+                && registry.contains(&AOCState {
+                    year: problem.0,
+                    day: problem.1,
+                    part: problem.2,
+                })
+        }
+    }
+}
+
+type ButtonColorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Interaction,
+        &'static mut UiColor,
+        &'static MenuButtonType,
+        &'static Children,
+        Option<&'static Disabled>,
+    ),
+    With<Button>,
+>;
+
+/// Last text of a button's children, its label.
+fn button_value(children: &Children, texts: &Query<&Text>) -> String {
+    children
+        .iter()
+        .filter_map(|child| texts.get(*child).ok())
+        .map(|text| text.sections[0].value.clone())
+        .last()
+        .unwrap_or_default()
+}
+
+fn update_button_availability(
+    mut commands: Commands,
+    menu_selection: Res<MenuData>,
+    registry: Res<PuzzleRegistry>,
+    input_selection: Res<InputSelection>,
+    buttons: Query<(Entity, &MenuButtonType, &Children, Option<&Disabled>)>,
+    texts: Query<&Text>,
+) {
+    for (entity, button_type, children, disabled) in buttons.iter() {
+        let value = button_value(children, &texts);
+        let problem = menu_selection.problem;
+        let available = is_available(button_type, &value, problem, &registry, &input_selection);
+        if available && disabled.is_some() {
+            commands.entity(entity).remove::<Disabled>();
+        } else if !available && disabled.is_none() {
+            commands.entity(entity).insert(Disabled);
+        }
+    }
+}
+
+/// Colours every button from its state, so the selected year, day, part and input stay
+/// highlighted after the cursor leaves them.
+fn update_button_colors(
+    menu_selection: Res<MenuData>,
+    mut buttons: ButtonColorQuery,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, mut color, button_type, children, disabled) in buttons.iter_mut() {
+        let value = children
+            .iter()
+            .filter_map(|child| texts.get(*child).ok())
+            .map(|text| text.sections[0].value.clone())
+            .last()
+            .unwrap_or_default();
+        let button_color = if disabled.is_some() {
+            DISABLED_BUTTON
+        } else if *interaction == Interaction::Clicked {
+            PRESSED_BUTTON
+        } else if is_selected(button_type, &value, menu_selection.problem) {
+            SELECTED_BUTTON
+        } else if *interaction == Interaction::Hovered {
+            HOVERED_BUTTON
+        } else {
+            NORMAL_BUTTON
+        };
+        if color.0 != button_color {
+            *color = button_color.into();
+        }
+        let text_color = if disabled.is_some() {
+            DISABLED_TEXT
+        } else {
+            NORMAL_TEXT
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if text.sections[0].style.color != text_color {
+                    text.sections[0].style.color = text_color;
+                }
+            }
+        }
+    }
+}
+
+fn highlight_focused_header(
+    menu_selection: Res<MenuData>,
+    mut headers: Query<(&MenuHeader, &mut Text)>,
+) {
+    if !menu_selection.is_changed() {
+        return;
+    }
+    for (header, mut text) in headers.iter_mut() {
+        text.sections[0].style.color = if header.0 == menu_selection.focus {
+            Color::GOLD
+        } else {
+            NORMAL_TEXT
+        };
+    }
+}
+
+fn update_puzzle_title(
+    menu_selection: Res<MenuData>,
+    registry: Res<PuzzleRegistry>,
+    mut query: Query<&mut Text, With<MenuTitle>>,
+) {
+    if !menu_selection.is_changed() {
+        return;
+    }
+    let (year, day, ..) = menu_selection.problem;
+    let title = match registry.title(year, day) {
+        Some(title) => format!("Day {}: {}", day, title),
+        None if day == 0 => String::new(),
+        None => format!("Day {}: not implemented", day),
+    };
+    for mut text in query.iter_mut() {
+        text.sections[0].value = title.clone();
+    }
+}
+
+/// Picks the custom input once a file is dropped on the window.
+fn pick_dropped_input(input_selection: Res<InputSelection>, mut menu_selection: ResMut<MenuData>) {
+    if input_selection.is_changed() && menu_selection.problem.3 != input_selection.source {
+        menu_selection.problem.3 = input_selection.source;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commons::puzzle::PuzzleInfo;
+
+    #[test]
+    fn test_step_selection() {
+        let registry = PuzzleRegistry {
+            puzzles: [1, 3]
+                .iter()
+                .map(|day| PuzzleInfo {
+                    year: 2018,
+                    day: *day,
+                    part: 1,
+                    title: "",
+                })
+                .collect(),
+        };
+        let problem = (2018, 0, 0, InputSource::Real);
+        let input_selection = InputSelection::new(None);
+        let step = |problem, step| {
+            step_selection(
+                &MenuButtonType::Day,
+                step,
+                problem,
+                &registry,
+                &input_selection,
+            )
+        };
+        assert_eq!(step(problem, 1), Some("1".to_string()));
+        assert_eq!(step(problem, -1), Some("3".to_string()));
+        assert_eq!(
+            step((2018, 1, 0, InputSource::Real), 1),
+            Some("3".to_string())
+        );
+        assert_eq!(step((2018, 3, 0, InputSource::Real), 1), None);
+    }
+
+    #[test]
+    fn test_type_day_digit() {
+        assert_eq!(type_day_digit(None, 4, 0.0), 4);
+        assert_eq!(type_day_digit(Some((1, 0.0)), 2, 0.5), 12);
+        assert_eq!(type_day_digit(Some((1, 0.0)), 2, 5.0), 2);
+        assert_eq!(type_day_digit(Some((3, 0.0)), 1, 0.5), 1);
+        assert_eq!(digit_key(&KeyCode::Numpad7), Some(7));
+        assert_eq!(digit_key(&KeyCode::A), None);
+    }
+
+    #[test]
+    fn test_is_available() {
+        let registry = PuzzleRegistry {
+            puzzles: vec![PuzzleInfo {
+                year: 2018,
+                day: 1,
+                part: 1,
+                title: "Chronal Calibration",
+            }],
+        };
+        let no_custom = InputSelection::new(None);
+        let available = |button_type, value, problem| {
+            is_available(&button_type, value, problem, &registry, &no_custom)
+        };
+        let problem = (0, 0, 0, InputSource::Real);
+        assert!(available(MenuButtonType::Day, "1", problem));
+        assert!(!available(MenuButtonType::Day, "2", problem));
+        assert!(!available(MenuButtonType::Launch, "", problem));
+        let problem = (2018, 1, 1, InputSource::Real);
+        assert!(available(MenuButtonType::Part, "1", problem));
+        assert!(!available(MenuButtonType::Part, "2", problem));
+        assert!(available(MenuButtonType::Launch, "", problem));
+        assert!(available(MenuButtonType::Input, "Sample", problem));
+        assert!(!available(MenuButtonType::Input, "Custom", problem));
+        let custom = (2018, 1, 1, InputSource::Custom);
+        assert!(!available(MenuButtonType::Launch, "", custom));
+        let with_custom = InputSelection::new(Some("my_input.txt".to_string()));
+        assert!(is_available(
+            &MenuButtonType::Input,
+            "Custom",
+            custom,
+            &registry,
+            &with_custom
+        ));
+        assert!(is_available(
+            &MenuButtonType::Launch,
+            "",
+            custom,
+            &registry,
+            &with_custom
+        ));
+    }
 }